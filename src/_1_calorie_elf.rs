@@ -1,13 +1,21 @@
-use std::fs;
+use crate::error::{parse_token, ParseError};
+use crate::input::paragraphs;
+use crate::solution::Solution;
 
-pub fn get_inputs() -> Vec<Vec<i32>> {
-    let input = fs::read_to_string("res/_1_calorie_elf.txt")
-        .expect("unable to open input file");
+pub mod rebalance;
+pub mod stats;
+pub mod stream;
+pub mod teams;
 
-    let input = input.split("\r\n\r\n");
-    let input = input.map(|cals| cals.split("\r\n"));
+use stats::Inventory;
+
+const DAY: u8 = 1;
+
+pub fn get_inputs(raw: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    let input = paragraphs(raw).into_iter();
     let input = input.map(|cals| {
-        cals.map(|cal| cal.parse::<i32>().expect("unable to parse str to i32"))
+        cals.into_iter()
+            .map(|line| parse_token(DAY, line, line.1.trim(), "a calorie count"))
             .collect()
     });
 
@@ -79,10 +87,10 @@ pub fn get_inputs() -> Vec<Vec<i32>> {
  * Find the Elf carrying the most Calories. How many total Calories is that
  * Elf carrying?
  */
-pub fn get_elf_with_most_calories(input: &Vec<Vec<i32>>) -> i32 {
+pub fn get_elf_with_most_calories(input: &[Vec<i32>]) -> i64 {
     let mut selected_elf_s_calorie = 0;
 
-    for food_cals in  input.iter() {
+    for food_cals in input.iter() {
         let cals = total_cal(food_cals);
         if cals >= selected_elf_s_calorie {
             selected_elf_s_calorie = cals;
@@ -94,47 +102,89 @@ pub fn get_elf_with_most_calories(input: &Vec<Vec<i32>>) -> i32 {
 
 /**
  * --- Part Two ---
- * By the time you calculate the answer to the Elves' question, they've already 
- * realized that the Elf carrying the most Calories of food might eventually run 
+ * By the time you calculate the answer to the Elves' question, they've already
+ * realized that the Elf carrying the most Calories of food might eventually run
  * out of snacks.
- * 
- * To avoid this unacceptable situation, the Elves would instead like to know 
- * the total Calories carried by the top three Elves carrying the most Calories. 
- * That way, even if one of those Elves runs out of snacks, they still have two 
+ *
+ * To avoid this unacceptable situation, the Elves would instead like to know
+ * the total Calories carried by the top three Elves carrying the most Calories.
+ * That way, even if one of those Elves runs out of snacks, they still have two
  * backups.
- * 
- * In the example above, the top three Elves are the fourth Elf (with 24000 
- * Calories), then the third Elf (with 11000 Calories), then the fifth Elf (with 
- * 10000 Calories). The sum of the Calories carried by these three elves is 
+ *
+ * In the example above, the top three Elves are the fourth Elf (with 24000
+ * Calories), then the third Elf (with 11000 Calories), then the fifth Elf (with
+ * 10000 Calories). The sum of the Calories carried by these three elves is
  * 45000.
- * 
- * Find the top three Elves carrying the most Calories. How many Calories are 
+ *
+ * Find the top three Elves carrying the most Calories. How many Calories are
  * those Elves carrying in total?
 */
-pub fn get_top_three_elfs_with_most_calories(input: &Vec<Vec<i32>>) -> i32 {
-    let mut thrid_highest = 0;
-    let mut second_highest = 0;
-    let mut first_hightest = 0;
+pub fn get_top_three_elfs_with_most_calories(input: &[Vec<i32>]) -> i64 {
+    Inventory::new(input).top_total(3)
+}
 
-    for food_cals in  input.iter() {
-        let cals = total_cal(food_cals);
-        if cals >= first_hightest {
-            thrid_highest = second_highest;
-            second_highest = first_hightest;
-            first_hightest = cals;
-        }
-        else if cals >= second_highest {
-            thrid_highest = second_highest;
-            second_highest = cals
-        }
-        else if cals >= thrid_highest {
-            thrid_highest = cals
-        }
+fn total_cal(food_cals: &[i32]) -> i64 {
+    food_cals.iter().map(|cal| i64::from(*cal)).sum()
+}
+
+pub struct CalorieElf;
+
+impl Solution for CalorieElf {
+    const INPUT_PATH: &'static str = "res/_1_calorie_elf.txt";
+
+    type Input = Vec<Vec<i32>>;
+    type Output = i64;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
     }
 
-    first_hightest + second_highest + thrid_highest
+    fn part1(inp: &Self::Input) -> Self::Output {
+        get_elf_with_most_calories(inp)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        get_top_three_elfs_with_most_calories(inp)
+    }
 }
 
-fn total_cal(food_cals: &Vec<i32>) -> i32 {
-    food_cals.iter().sum()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    pub(super) const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn part1_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(get_elf_with_most_calories(&inp), 24000);
+    }
+
+    #[test]
+    fn part2_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(get_top_three_elfs_with_most_calories(&inp), 45000);
+    }
+
+    #[test]
+    fn real_input() {
+        let (ans_1, _) = solve_real_input::<CalorieElf>();
+        assert_eq!(ans_1, 71934);
+    }
 }
@@ -0,0 +1,351 @@
+use std::fmt;
+
+use super::total_cal;
+
+/// What every elf's total should end up as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Goal {
+    /// At most `tolerance` calories away from the mean.
+    NearMean { tolerance: i64 },
+    /// At least this many calories.
+    Floor(i64),
+}
+
+/// One food item handed from an elf to another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub from: usize,
+    pub to: usize,
+    pub calories: i32,
+}
+
+impl fmt::Display for Transfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.from, self.to, self.calories)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub transfers: Vec<Transfer>,
+    /// Every elf's total once the transfers are done.
+    pub totals: Vec<i64>,
+}
+
+/// One `<from> <to> <calories>` line per transfer, elves counted from 0.
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# from to calories")?;
+        for transfer in self.transfers.iter() {
+            writeln!(f, "{transfer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Inputs with at most this many ways of handing out their items are searched
+/// exhaustively for the fewest transfers.
+pub const EXACT_LIMIT: u64 = 1 << 16;
+
+/// Plans item transfers until every elf meets `goal`. Small inputs, see
+/// [`EXACT_LIMIT`], get a plan with the fewest transfers. Larger ones are
+/// planned greedily, which keeps the plan short but not minimal, and may give
+/// up on a goal that another order of transfers would reach.
+pub fn plan(input: &[Vec<i32>], goal: Goal) -> Result<Plan, String> {
+    let items = input.iter().map(|food_cals| food_cals.len() as u32).sum();
+    let size = (input.len() as u64).checked_pow(items);
+
+    match size {
+        Some(size) if size <= EXACT_LIMIT => plan_exact(input, goal),
+        _ => plan_greedy(input, goal),
+    }
+}
+
+fn is_met(goal: Goal, totals: &[i64]) -> bool {
+    match goal {
+        Goal::NearMean { tolerance } => {
+            let mean = totals.iter().sum::<i64>() as f64 / totals.len() as f64;
+            totals
+                .iter()
+                .all(|total| (*total as f64 - mean).abs() <= tolerance as f64)
+        }
+        Goal::Floor(floor) => totals.iter().all(|total| *total >= floor),
+    }
+}
+
+/// Tries every set of transfers, fewest first. A plan never needs to move an
+/// item twice, so each item is either kept or handed to one other elf.
+fn plan_exact(input: &[Vec<i32>], goal: Goal) -> Result<Plan, String> {
+    let items: Vec<(usize, i32)> = input
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, food_cals)| food_cals.iter().map(move |cal| (elf, *cal)))
+        .collect();
+    let mut totals: Vec<i64> = input.iter().map(|food_cals| total_cal(food_cals)).collect();
+
+    for count in 0..=items.len() {
+        let mut transfers = vec![];
+        if search(&items, goal, count, &mut totals, &mut transfers) {
+            return Ok(Plan { transfers, totals });
+        }
+    }
+
+    Err(match goal {
+        Goal::NearMean { tolerance } => {
+            format!("no transfers bring every elf within {tolerance} calories of the mean")
+        }
+        Goal::Floor(floor) => format!("no transfers give every elf {floor} calories"),
+    })
+}
+
+/// Hands `count` of `items` to other elves, leaving `totals` and `transfers`
+/// as they are once `goal` is met.
+fn search(
+    items: &[(usize, i32)],
+    goal: Goal,
+    count: usize,
+    totals: &mut [i64],
+    transfers: &mut Vec<Transfer>,
+) -> bool {
+    if count == 0 {
+        return is_met(goal, totals);
+    }
+
+    for (idx, (from, calories)) in items.iter().enumerate() {
+        if items.len() - idx < count {
+            break;
+        }
+
+        for to in (0..totals.len()).filter(|to| to != from) {
+            totals[*from] -= i64::from(*calories);
+            totals[to] += i64::from(*calories);
+            transfers.push(Transfer {
+                from: *from,
+                to,
+                calories: *calories,
+            });
+
+            if search(&items[idx + 1..], goal, count - 1, totals, transfers) {
+                return true;
+            }
+
+            transfers.pop();
+            totals[to] -= i64::from(*calories);
+            totals[*from] += i64::from(*calories);
+        }
+    }
+
+    false
+}
+
+/// Picks each transfer to close as much of the gap as a single item can.
+fn plan_greedy(input: &[Vec<i32>], goal: Goal) -> Result<Plan, String> {
+    let mut elves = input.to_vec();
+    let mut totals: Vec<i64> = elves.iter().map(|food_cals| total_cal(food_cals)).collect();
+    let mut transfers = vec![];
+
+    while let Some((from, item, to)) = match goal {
+        Goal::NearMean { tolerance } => next_towards_mean(&elves, &totals, tolerance)?,
+        Goal::Floor(floor) => next_above_floor(&elves, &totals, floor)?,
+    } {
+        let calories = elves[from].swap_remove(item);
+        elves[to].push(calories);
+        totals[from] -= i64::from(calories);
+        totals[to] += i64::from(calories);
+
+        transfers.push(Transfer { from, to, calories });
+    }
+
+    Ok(Plan { transfers, totals })
+}
+
+type Move = Option<(usize, usize, usize)>;
+
+/// Moves an item from the richest to the poorest elf, or else between the two
+/// elves it narrows the gap of the most, as close to half their gap as there
+/// is. Any item smaller than the gap lowers the sum of the squared totals, so
+/// the planning always ends.
+fn next_towards_mean(elves: &[Vec<i32>], totals: &[i64], tolerance: i64) -> Result<Move, String> {
+    if elves.is_empty() || is_met(Goal::NearMean { tolerance }, totals) {
+        return Ok(None);
+    }
+
+    let richest = (0..totals.len()).max_by_key(|elf| totals[*elf]).unwrap();
+    let poorest = (0..totals.len()).min_by_key(|elf| totals[*elf]).unwrap();
+    let gap = totals[richest] - totals[poorest];
+    if let Some(item) = closest_to_half(&elves[richest], gap) {
+        return Ok(Some((richest, item, poorest)));
+    }
+
+    let mut best: Option<(i64, (usize, usize, usize))> = None;
+    for from in 0..totals.len() {
+        for to in (0..totals.len()).filter(|to| totals[*to] < totals[from]) {
+            let gap = totals[from] - totals[to];
+            let Some(item) = closest_to_half(&elves[from], gap) else {
+                continue;
+            };
+
+            let calories = i64::from(elves[from][item]);
+            let narrowed = calories * (gap - calories);
+            if best.is_none_or(|(most, _)| narrowed > most) {
+                best = Some((narrowed, (from, item, to)));
+            }
+        }
+    }
+
+    match best {
+        Some((_, transfer)) => Ok(Some(transfer)),
+        None => Err(String::from(
+            "no single item can move two elves closer to each other",
+        )),
+    }
+}
+
+/// The item of `food_cals` closest to half of `gap` among those smaller than
+/// it, which narrows the gap the most.
+fn closest_to_half(food_cals: &[i32], gap: i64) -> Option<usize> {
+    food_cals
+        .iter()
+        .enumerate()
+        .filter(|(_, cal)| **cal > 0 && i64::from(**cal) < gap)
+        .min_by_key(|(_, cal)| (2 * i64::from(**cal) - gap).abs())
+        .map(|(item, _)| item)
+}
+
+/// Tops up the poorest elf below `floor` from elves that stay at or above it,
+/// with the smallest item covering its whole deficit or else the largest
+/// item that can be spared.
+fn next_above_floor(elves: &[Vec<i32>], totals: &[i64], floor: i64) -> Result<Move, String> {
+    let Some(poorest) = (0..totals.len())
+        .filter(|elf| totals[*elf] < floor)
+        .min_by_key(|elf| totals[*elf])
+    else {
+        return Ok(None);
+    };
+
+    let needed = floor * totals.len() as i64;
+    let total: i64 = totals.iter().sum();
+    if total < needed {
+        return Err(format!(
+            "{total} calories cannot give {} elves {floor} each",
+            totals.len()
+        ));
+    }
+
+    let deficit = floor - totals[poorest];
+    let spare: Vec<(usize, usize, i64)> = elves
+        .iter()
+        .enumerate()
+        .filter(|(elf, _)| *elf != poorest)
+        .flat_map(|(elf, food_cals)| {
+            food_cals
+                .iter()
+                .enumerate()
+                .map(move |(item, cal)| (elf, item, i64::from(*cal)))
+        })
+        .filter(|(elf, _, cal)| *cal > 0 && totals[*elf] - cal >= floor)
+        .collect();
+
+    let covering = spare
+        .iter()
+        .filter(|(_, _, cal)| *cal >= deficit)
+        .min_by_key(|(_, _, cal)| *cal);
+    let largest = spare.iter().max_by_key(|(_, _, cal)| *cal);
+
+    match covering.or(largest) {
+        Some((from, item, _)) => Ok(Some((*from, *item, poorest))),
+        None => Err(format!(
+            "no elf can spare an item for elf {poorest} without dropping below {floor}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{get_inputs, tests::EXAMPLE};
+    use super::*;
+
+    #[test]
+    fn plan_near_mean_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        let plan = plan(&inp, Goal::NearMean { tolerance: 3000 }).unwrap();
+
+        assert_eq!(
+            plan.transfers,
+            [
+                Transfer {
+                    from: 3,
+                    to: 1,
+                    calories: 9000,
+                },
+                Transfer {
+                    from: 3,
+                    to: 0,
+                    calories: 7000,
+                },
+            ]
+        );
+        assert_eq!(plan.totals, [13000, 13000, 11000, 8000, 10000]);
+        assert_eq!(plan.to_string(), "# from to calories\n3 1 9000\n3 0 7000\n");
+
+        let err = super::plan(&inp, Goal::NearMean { tolerance: 0 }).unwrap_err();
+        assert!(err.starts_with("no single item"), "{err}");
+    }
+
+    #[test]
+    fn plan_near_mean_past_the_richest() {
+        // The richest elf's only item is larger than its gap to the poorest
+        let inp = vec![vec![9], vec![1], vec![4, 4], vec![6]];
+        let goal = Goal::NearMean { tolerance: 3 };
+
+        let greedy = plan_greedy(&inp, goal).unwrap();
+        assert_eq!(
+            greedy.transfers,
+            [Transfer {
+                from: 2,
+                to: 1,
+                calories: 4,
+            }]
+        );
+        assert_eq!(greedy.totals, [9, 5, 4, 6]);
+        assert_eq!(super::plan(&inp, goal).unwrap(), greedy);
+    }
+
+    #[test]
+    fn plan_fewest_transfers() {
+        let inp = vec![vec![], vec![9, 2], vec![2, 6, 2]];
+        let goal = Goal::NearMean { tolerance: 2 };
+
+        assert_eq!(plan_greedy(&inp, goal).unwrap().transfers.len(), 3);
+        let plan = super::plan(&inp, goal).unwrap();
+        assert_eq!(plan.transfers.len(), 2);
+        assert!(plan.totals.iter().all(|total| (total - 7).abs() <= 2));
+
+        assert_eq!(
+            super::plan(&[vec![3], vec![]], Goal::NearMean { tolerance: 1 }),
+            Err(String::from(
+                "no transfers bring every elf within 1 calories of the mean"
+            ))
+        );
+    }
+
+    #[test]
+    fn plan_floor_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        let plan = plan(&inp, Goal::Floor(8000)).unwrap();
+
+        assert_eq!(plan.to_string(), "# from to calories\n3 1 7000\n3 0 8000\n");
+        assert_eq!(plan.totals, [14000, 11000, 11000, 9000, 10000]);
+
+        assert!(super::plan(&inp, Goal::Floor(4000))
+            .unwrap()
+            .transfers
+            .is_empty());
+        assert_eq!(
+            super::plan(&inp, Goal::Floor(12000)),
+            Err(String::from(
+                "55000 calories cannot give 5 elves 12000 each"
+            ))
+        );
+    }
+}
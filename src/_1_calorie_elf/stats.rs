@@ -0,0 +1,145 @@
+use super::total_cal;
+
+/// One elf's share of the inventory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElfTotal {
+    /// Position of the elf in the input, from 0.
+    pub index: usize,
+    pub items: usize,
+    pub total: i64,
+}
+
+/// Every elf's calories, ranked from the most to the fewest.
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    ranking: Vec<ElfTotal>,
+}
+
+impl Inventory {
+    pub fn new(input: &[Vec<i32>]) -> Inventory {
+        let mut ranking: Vec<ElfTotal> = input
+            .iter()
+            .enumerate()
+            .map(|(index, food_cals)| ElfTotal {
+                index,
+                items: food_cals.len(),
+                total: total_cal(food_cals),
+            })
+            .collect();
+
+        // Elves carrying as much keep their input order
+        ranking.sort_by(|a, b| b.total.cmp(&a.total).then(a.index.cmp(&b.index)));
+
+        Inventory { ranking }
+    }
+
+    pub fn ranking(&self) -> &[ElfTotal] {
+        &self.ranking
+    }
+
+    /// The `k` elves carrying the most, or every elf when there are fewer.
+    pub fn top(&self, k: usize) -> &[ElfTotal] {
+        &self.ranking[..k.min(self.ranking.len())]
+    }
+
+    pub fn top_total(&self, k: usize) -> i64 {
+        self.top(k).iter().map(|elf| elf.total).sum()
+    }
+
+    /// Rank of the elf at `index` in the input, from 1.
+    pub fn rank_of(&self, index: usize) -> Option<usize> {
+        self.ranking
+            .iter()
+            .position(|elf| elf.index == index)
+            .map(|rank| rank + 1)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.ranking.is_empty() {
+            return None;
+        }
+
+        Some(self.top_total(self.ranking.len()) as f64 / self.ranking.len() as f64)
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// Total below which `p` percent of the elves fall, interpolating between
+    /// the two closest elves. `p` goes from 0 to 100.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.ranking.is_empty() || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+
+        // The ranking runs from the most to the fewest calories
+        let total_at = |rank: usize| self.ranking[self.ranking.len() - 1 - rank].total as f64;
+
+        let rank = p / 100.0 * (self.ranking.len() - 1) as f64;
+        let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+        Some(total_at(below) + (total_at(above) - total_at(below)) * rank.fract())
+    }
+
+    /// Population standard deviation of the totals.
+    pub fn std_dev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let variance = self
+            .ranking
+            .iter()
+            .map(|elf| (elf.total as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.ranking.len() as f64;
+
+        Some(variance.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{get_inputs, tests::EXAMPLE};
+    use super::*;
+
+    #[test]
+    fn rank_example() {
+        let inventory = Inventory::new(&get_inputs(EXAMPLE).unwrap());
+
+        assert_eq!(
+            inventory.top(2),
+            [
+                ElfTotal {
+                    index: 3,
+                    items: 3,
+                    total: 24000,
+                },
+                ElfTotal {
+                    index: 2,
+                    items: 2,
+                    total: 11000,
+                },
+            ]
+        );
+        assert_eq!(inventory.top(10).len(), 5);
+        assert_eq!(inventory.top_total(3), 45000);
+        assert_eq!(inventory.rank_of(0), Some(4));
+        assert_eq!(inventory.rank_of(5), None);
+    }
+
+    #[test]
+    fn statistics_example() {
+        let inventory = Inventory::new(&get_inputs(EXAMPLE).unwrap());
+
+        assert_eq!(inventory.mean(), Some(11000.0));
+        assert_eq!(inventory.median(), Some(10000.0));
+        assert_eq!(inventory.percentile(0.0), Some(4000.0));
+        assert_eq!(inventory.percentile(25.0), Some(6000.0));
+        assert_eq!(inventory.percentile(90.0), Some(18800.0));
+        assert_eq!(inventory.percentile(100.5), None);
+        assert_eq!(inventory.std_dev().map(f64::round), Some(6986.0));
+
+        let empty = Inventory::new(&[]);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.median(), None);
+        assert_eq!(empty.std_dev(), None);
+    }
+}
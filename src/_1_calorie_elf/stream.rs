@@ -0,0 +1,223 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+};
+
+use super::stats::ElfTotal;
+use super::DAY;
+use crate::error::{parse_token, ParseError};
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "unable to read the inventory: {err}"),
+            StreamError::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> StreamError {
+        StreamError::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> StreamError {
+        StreamError::Parse(err)
+    }
+}
+
+// Ordered by total, then earlier elves first, so the heap's smallest entry is
+// the one to drop
+type Ranked = Reverse<(i64, Reverse<usize>, usize)>;
+
+/// The `k` elves carrying the most, kept while elves are pushed one by one.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Ranked>,
+    elves: usize,
+    items: u64,
+    /// Wide enough for any number of `i64` totals.
+    total: i128,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> TopK {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+            elves: 0,
+            items: 0,
+            total: 0,
+        }
+    }
+
+    pub fn push(&mut self, elf: ElfTotal) {
+        self.elves += 1;
+        self.items += elf.items as u64;
+        self.total += i128::from(elf.total);
+
+        self.heap
+            .push(Reverse((elf.total, Reverse(elf.index), elf.items)));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// The kept elves, from the most to the fewest calories.
+    pub fn top(&self) -> Vec<ElfTotal> {
+        // Sorted smallest `Reverse` first, which is the largest total
+        self.heap
+            .clone()
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index), items))| ElfTotal {
+                index,
+                items,
+                total,
+            })
+            .collect()
+    }
+
+    pub fn top_total(&self) -> i128 {
+        self.heap
+            .iter()
+            .map(|Reverse((total, _, _))| i128::from(*total))
+            .sum()
+    }
+
+    /// Number of elves pushed so far.
+    pub fn elves(&self) -> usize {
+        self.elves
+    }
+
+    /// Number of food items of every elf pushed so far.
+    pub fn items(&self) -> u64 {
+        self.items
+    }
+
+    /// Calories of every elf pushed so far.
+    pub fn total(&self) -> i128 {
+        self.total
+    }
+}
+
+/// Reads an inventory line by line from `reader`, keeping only the `k` elves
+/// carrying the most, so memory does not grow with the inventory.
+pub fn read_top_k(mut reader: impl BufRead, k: usize) -> Result<TopK, StreamError> {
+    let mut top_k = TopK::new(k);
+    let mut buf = String::new();
+    let mut line_number = 0;
+    let mut elf: Option<ElfTotal> = None;
+
+    loop {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let text = buf.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            if let Some(elf) = elf.take() {
+                top_k.push(elf);
+            }
+            continue;
+        }
+
+        let line = (line_number, text);
+        let cal: i64 = parse_token(DAY, line, text.trim(), "a calorie count")?;
+        let index = top_k.elves();
+        let elf = elf.get_or_insert(ElfTotal {
+            index,
+            items: 0,
+            total: 0,
+        });
+        elf.items += 1;
+        elf.total = elf.total.checked_add(cal).ok_or_else(|| {
+            ParseError::at(
+                DAY,
+                line,
+                text.trim(),
+                "a calorie count the elf's total can hold",
+            )
+        })?;
+    }
+
+    if let Some(elf) = elf {
+        top_k.push(elf);
+    }
+
+    Ok(top_k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::EXAMPLE;
+    use super::*;
+
+    #[test]
+    fn stream_example() {
+        let top_k = read_top_k(EXAMPLE.as_bytes(), 3).unwrap();
+
+        let totals: Vec<(usize, i64)> = top_k
+            .top()
+            .iter()
+            .map(|elf| (elf.index, elf.total))
+            .collect();
+        assert_eq!(totals, [(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(top_k.top_total(), 45000);
+        assert_eq!(top_k.elves(), 5);
+        assert_eq!(top_k.items(), 10);
+        assert_eq!(top_k.total(), 55000);
+
+        assert!(read_top_k(EXAMPLE.as_bytes(), 0).unwrap().top().is_empty());
+    }
+
+    #[test]
+    fn stream_totals_past_i32() {
+        let raw = "3000000000\r\n3000000000\r\n\r\n\r\n1\r\n";
+        let top_k = read_top_k(raw.as_bytes(), 1).unwrap();
+
+        assert_eq!(top_k.top_total(), 6_000_000_000);
+        assert_eq!(top_k.elves(), 2);
+
+        let max = i64::MAX.to_string();
+        let raw = format!("{max}\n\n{max}\n\n{max}\n");
+        let top_k = read_top_k(raw.as_bytes(), 2).unwrap();
+        assert_eq!(top_k.top_total(), 2 * i128::from(i64::MAX));
+        assert_eq!(top_k.total(), 3 * i128::from(i64::MAX));
+    }
+
+    #[test]
+    fn stream_reports_line() {
+        let err = read_top_k("1000\n\n20x0\n".as_bytes(), 3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 1 input, line 3, column 1: expected a calorie count, found `20x0`"
+        );
+
+        let raw = format!("1\n{}\n", i64::MAX);
+        let err = read_top_k(raw.as_bytes(), 3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "day 1 input, line 2, column 1: expected a calorie count the elf's total can hold, found `{}`",
+                i64::MAX
+            )
+        );
+    }
+}
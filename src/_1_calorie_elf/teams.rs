@@ -0,0 +1,283 @@
+use std::{collections::BinaryHeap, fmt};
+
+use super::total_cal;
+
+/// Largest number of splits `Mode::Exact` takes on, there are `teams ^ elves`
+/// of them.
+pub const EXACT_LIMIT: u64 = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// Searches every split for the smallest spread.
+    Exact,
+    /// Hands the elves, most calories first, to the team with the fewest.
+    Greedy,
+    /// Karmarkar-Karp differencing: merges the two most uneven partial
+    /// splits, pairing the largest team of one with the smallest of the other.
+    KarmarkarKarp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    /// The elves of each team, by their position in the input.
+    pub teams: Vec<Vec<usize>>,
+    pub totals: Vec<i64>,
+}
+
+impl Partition {
+    fn new(mut teams: Vec<Vec<usize>>, elf_totals: &[i64]) -> Partition {
+        for team in teams.iter_mut() {
+            team.sort_unstable();
+        }
+        let totals = teams
+            .iter()
+            .map(|team| team.iter().map(|elf| elf_totals[*elf]).sum())
+            .collect();
+
+        Partition { teams, totals }
+    }
+
+    /// Calories between the best and the worst supplied team.
+    pub fn spread(&self) -> i64 {
+        let max = self.totals.iter().max().copied().unwrap_or(0);
+        let min = self.totals.iter().min().copied().unwrap_or(0);
+        max - min
+    }
+}
+
+impl fmt::Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, (team, total)) in self.teams.iter().zip(self.totals.iter()).enumerate() {
+            let elves: Vec<String> = team.iter().map(|elf| elf.to_string()).collect();
+            writeln!(f, "team {}: {total} ({})", idx + 1, elves.join(" "))?;
+        }
+        write!(f, "spread: {}", self.spread())
+    }
+}
+
+/// Splits the elves into `teams` teams with totals as even as `mode` manages.
+pub fn partition(input: &[Vec<i32>], teams: usize, mode: Mode) -> Result<Partition, String> {
+    if teams == 0 {
+        return Err(String::from("there must be at least one team"));
+    }
+
+    let elf_totals: Vec<i64> = input.iter().map(|food_cals| total_cal(food_cals)).collect();
+    let splits = u32::try_from(elf_totals.len())
+        .ok()
+        .and_then(|elves| (teams as u64).checked_pow(elves));
+    let split = match mode {
+        Mode::Exact if splits.is_none_or(|splits| splits > EXACT_LIMIT) => {
+            return Err(format!(
+                "{} elves are too many to split exactly into {teams} teams, the limit is {EXACT_LIMIT} splits",
+                elf_totals.len()
+            ))
+        }
+        Mode::Exact => split_exact(&elf_totals, teams),
+        Mode::Greedy => split_greedy(&elf_totals, teams),
+        Mode::KarmarkarKarp => split_karmarkar_karp(&elf_totals, teams),
+    };
+
+    Ok(Partition::new(split, &elf_totals))
+}
+
+/// Elves from the most to the fewest calories.
+fn by_calories(elf_totals: &[i64]) -> Vec<usize> {
+    let mut elves: Vec<usize> = (0..elf_totals.len()).collect();
+    elves.sort_by_key(|elf| std::cmp::Reverse(elf_totals[*elf]));
+    elves
+}
+
+fn split_greedy(elf_totals: &[i64], teams: usize) -> Vec<Vec<usize>> {
+    let mut split = vec![vec![]; teams];
+    let mut totals = vec![0; teams];
+
+    for elf in by_calories(elf_totals) {
+        let team = (0..teams).min_by_key(|team| totals[*team]).unwrap();
+        split[team].push(elf);
+        totals[team] += elf_totals[elf];
+    }
+
+    split
+}
+
+/// A partial split, its team totals kept from the largest to the smallest.
+type Partial = Vec<(i64, Vec<usize>)>;
+
+fn split_karmarkar_karp(elf_totals: &[i64], teams: usize) -> Vec<Vec<usize>> {
+    let mut partials: Vec<Partial> = vec![];
+    // Spread and position of every partial, the most uneven first
+    let mut heap = BinaryHeap::new();
+
+    for (elf, total) in elf_totals.iter().enumerate() {
+        let mut split = vec![(0, vec![]); teams];
+        split[0] = (*total, vec![elf]);
+
+        heap.push((*total, partials.len()));
+        partials.push(split);
+    }
+
+    while heap.len() > 1 {
+        let (_, first) = heap.pop().unwrap();
+        let (_, second) = heap.pop().unwrap();
+        let first = std::mem::take(&mut partials[first]);
+        let second = std::mem::take(&mut partials[second]);
+
+        let mut merged: Partial = first
+            .into_iter()
+            .zip(second.into_iter().rev())
+            .map(|((total_a, mut elves_a), (total_b, elves_b))| {
+                elves_a.extend(elves_b);
+                (total_a + total_b, elves_a)
+            })
+            .collect();
+        merged.sort_by_key(|(total, _)| std::cmp::Reverse(*total));
+
+        let spread = merged[0].0 - merged[teams - 1].0;
+        heap.push((spread, partials.len()));
+        partials.push(merged);
+    }
+
+    match heap.pop() {
+        Some((_, last)) => std::mem::take(&mut partials[last])
+            .into_iter()
+            .map(|(_, elves)| elves)
+            .collect(),
+        None => vec![vec![]; teams],
+    }
+}
+
+fn split_exact(elf_totals: &[i64], teams: usize) -> Vec<Vec<usize>> {
+    let elves = by_calories(elf_totals);
+    let mut search = ExactSearch {
+        elf_totals,
+        elves: &elves,
+        remaining: elves.iter().map(|elf| elf_totals[*elf]).sum(),
+        totals: vec![0; teams],
+        assigned: vec![0; elves.len()],
+        best: (i64::MAX, vec![]),
+    };
+    search.assign(0);
+
+    let mut split = vec![vec![]; teams];
+    for (pos, team) in search.best.1.iter().enumerate() {
+        split[*team].push(elves[pos]);
+    }
+    split
+}
+
+struct ExactSearch<'a> {
+    elf_totals: &'a [i64],
+    elves: &'a [usize],
+    /// Calories of the elves not assigned yet.
+    remaining: i64,
+    totals: Vec<i64>,
+    assigned: Vec<usize>,
+    best: (i64, Vec<usize>),
+}
+
+impl ExactSearch<'_> {
+    fn assign(&mut self, pos: usize) {
+        let max = self.totals.iter().max().copied().unwrap();
+        // No team can end up above what the smallest could still reach
+        let lowest_reachable = self.totals.iter().min().unwrap() + self.remaining;
+        if max - lowest_reachable.min(max) >= self.best.0 {
+            return;
+        }
+
+        if pos == self.elves.len() {
+            let spread = max - self.totals.iter().min().unwrap();
+            self.best = (spread, self.assigned.clone());
+            return;
+        }
+
+        let total = self.elf_totals[self.elves[pos]];
+        self.remaining -= total;
+        for team in 0..self.totals.len() {
+            // Empty teams are interchangeable, trying one is enough
+            if self.totals[team] == 0 && self.totals[..team].contains(&0) {
+                continue;
+            }
+
+            self.totals[team] += total;
+            self.assigned[pos] = team;
+            self.assign(pos + 1);
+            self.totals[team] -= total;
+        }
+        self.remaining += total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{get_inputs, tests::EXAMPLE};
+    use super::*;
+
+    #[test]
+    fn partition_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+
+        for mode in [Mode::Exact, Mode::Greedy, Mode::KarmarkarKarp] {
+            let two = partition(&inp, 2, mode).unwrap();
+            assert_eq!(two.spread(), 1000, "{mode:?}");
+
+            let mut elves: Vec<usize> = two.teams.concat();
+            elves.sort_unstable();
+            assert_eq!(elves, [0, 1, 2, 3, 4], "{mode:?}");
+        }
+
+        let three = partition(&inp, 3, Mode::Exact).unwrap();
+        assert_eq!(three.spread(), 9000);
+        assert_eq!(
+            three.to_string(),
+            "team 1: 24000 (3)\nteam 2: 15000 (1 2)\nteam 3: 16000 (0 4)\nspread: 9000"
+        );
+
+        assert!(partition(&inp, 0, Mode::Greedy).is_err());
+    }
+
+    #[test]
+    fn exact_beats_greedy() {
+        // Greedy pairs 8 with 5 and 7 with 6 and 4
+        let inp: Vec<Vec<i32>> = [8, 7, 6, 5, 4].iter().map(|cal| vec![*cal]).collect();
+
+        assert_eq!(partition(&inp, 2, Mode::Greedy).unwrap().spread(), 4);
+        assert_eq!(partition(&inp, 2, Mode::Exact).unwrap().spread(), 0);
+
+        // The limit shrinks as the number of teams grows
+        assert!(partition(&vec![vec![1]; 16], 2, Mode::Exact).is_ok());
+        assert!(partition(&vec![vec![1]; 17], 2, Mode::Exact).is_err());
+        assert!(partition(&vec![vec![1]; 10], 3, Mode::Exact).is_ok());
+        assert_eq!(
+            partition(&vec![vec![1]; 11], 3, Mode::Exact),
+            Err(String::from(
+                "11 elves are too many to split exactly into 3 teams, the limit is 65536 splits"
+            ))
+        );
+    }
+
+    #[test]
+    fn partition_real_input() {
+        let raw = std::fs::read_to_string("res/_1_calorie_elf.txt").unwrap();
+        let inp = get_inputs(&raw).unwrap();
+
+        let elf_totals: Vec<i64> = inp.iter().map(|food_cals| total_cal(food_cals)).collect();
+        let largest = elf_totals.iter().max().copied().unwrap();
+
+        for mode in [Mode::Greedy, Mode::KarmarkarKarp] {
+            let split = partition(&inp, 3, mode).unwrap();
+            assert_eq!(split.teams.len(), 3, "{mode:?}");
+
+            let mut elves: Vec<usize> = split.teams.concat();
+            elves.sort_unstable();
+            assert!(elves.into_iter().eq(0..inp.len()), "{mode:?}");
+            assert_eq!(
+                split.totals.iter().sum::<i64>(),
+                elf_totals.iter().sum(),
+                "{mode:?}"
+            );
+
+            // Neither leaves a gap larger than the elf carrying the most
+            assert!(split.spread() <= largest, "{mode:?}");
+        }
+    }
+}
@@ -1,102 +1,77 @@
-use std::fs;
-
-pub fn get_inputs() -> Vec<(String, String)> {
-    let input =
-        fs::read_to_string("res/_2_rock_paper_sissor.txt").expect("unable to open input file");
-
-    input
-        .split("\r\n")
-        .map(|each| {
-            let contestent_inputs: Vec<String> = String::from(each)
-                .split_whitespace()
-                .map(String::from)
-                .collect();
-
-            let input_1 = contestent_inputs.get(0).unwrap().clone();
-            let input_2 = contestent_inputs.get(1).unwrap().clone();
-            (input_1, input_2)
-        })
-        .collect()
-}
-
-#[derive(PartialEq, Clone)]
-enum Resolution {
-    Win,
-    Draw,
-    Loss,
+use crate::error::ParseError;
+use crate::input::{lines, Line};
+use crate::solution::Solution;
+
+pub mod decode;
+pub mod engine;
+pub mod report;
+pub mod solver;
+pub mod tournament;
+
+pub use decode::{Play, Scheme};
+pub use engine::{Game, Resolution, Scoring, RPS};
+
+const DAY: u8 = 2;
+
+/// A token of the guide, with its line and column counted from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
 }
 
-impl Resolution {
-    fn new(round_end_type: &str) -> Resolution {
-        match round_end_type {
-            "X" => Resolution::Loss,
-            "Y" => Resolution::Draw,
-            _ => Resolution::Win,
+impl Token {
+    /// `token` must be a slice of the line's text.
+    fn new((line, text): Line, token: &str) -> Token {
+        Token {
+            line,
+            column: token.as_ptr() as usize - text.as_ptr() as usize + 1,
+            text: String::from(token),
         }
     }
 
-    fn calc_score(&self, played_shape: &RPS) -> i32 {
-        match self {
-            Resolution::Loss => played_shape.0,
-            Resolution::Draw => 3 + played_shape.0,
-            Resolution::Win => 6 + played_shape.0,
+    /// Reports this token where `expected` should have been.
+    pub fn unexpected(&self, expected: &str) -> ParseError {
+        ParseError {
+            day: DAY,
+            line: self.line,
+            column: self.column,
+            expected: String::from(expected),
+            found: format!("`{}`", self.text),
         }
     }
 }
 
-#[derive(Clone)]
-struct RPS(i32);
-
-impl RPS {
-    fn new(shape: &str) -> RPS {
-        match shape {
-            "A" | "X" => RPS(1),
-            "B" | "Y" => RPS(2),
-            _ => RPS(3),
-        }
-    }
-
-    fn fight(&self, other: &Self) -> i32 {
-        self.fight_resolution(other).calc_score(self)
-    }
-
-    fn fight_resolution(&self, other: &Self) -> Resolution {
-        if self.0 == other.0 {
-            return Resolution::Draw;
-        }
-
-        if self.0 == 3 && other.0 == 1 {
-            return Resolution::Loss;
-        }
-
-        if (self.0 == 1 && other.0 == 3) || self.0 > other.0 {
-            return Resolution::Win;
-        }
-
-        Resolution::Loss
-    }
-
-    fn get_other_shapes(&self) -> (RPS, RPS) {
-        let shape_1 = (self.0 + 1) % 3;
-        let shape_1 = RPS(if shape_1 == 0 { 3 } else { shape_1 });
-
-        let shape_2 = (self.0 + 2) % 3;
-        let shape_2 = RPS(if shape_2 == 0 { 3 } else { shape_2 });
-
-        return (shape_1, shape_2);
-    }
-
-    fn shape_base_on_resolution(&self, resolution: Resolution) -> (Self, Resolution) {
-        if resolution == Resolution::Draw {
-            return (self.clone(), resolution);
-        }
+/// A line of the guide: the opponent's token, then the response's.
+pub type Entry = (Token, Token);
+
+/// Reads the guide's two columns of tokens, leaving it to a [`Scheme`] to make
+/// sense of them.
+pub fn get_inputs(raw: &str) -> Result<Vec<Entry>, ParseError> {
+    lines(raw)
+        .into_iter()
+        .map(|line| {
+            let mut contestent_inputs = line.1.split_whitespace();
+
+            let input_1 = contestent_inputs
+                .next()
+                .ok_or_else(|| ParseError::missing(DAY, line, "the opponent's shape"))?;
+            let input_2 = contestent_inputs
+                .next()
+                .ok_or_else(|| ParseError::missing(DAY, line, "the second column"))?;
+            if let Some(extra) = contestent_inputs.next() {
+                return Err(ParseError::at(DAY, line, extra, "end of line"));
+            }
+
+            Ok((Token::new(line, input_1), Token::new(line, input_2)))
+        })
+        .collect()
+}
 
-        let (shape_1, shape_2) = self.get_other_shapes();
-        if shape_1.fight_resolution(self) == resolution {
-            return (shape_1, resolution);
-        }
-        (shape_2, resolution)
-    }
+/// The puzzle's game read with `scheme`, `SHAPES_SCHEME` or `OUTCOMES_SCHEME`.
+fn puzzle_scheme(scheme: &str) -> Scheme {
+    Scheme::new(&Game::rock_paper_scissors(), scheme).unwrap()
 }
 
 /**
@@ -156,14 +131,8 @@ impl RPS {
  * What would your total score be if everything goes exactly according to your
  * strategy guide?
 */
-pub fn score(inp: &Vec<(String, String)>) -> i32 {
-    let mut score = 0;
-
-    for (player_1_shape, player_2_shape) in inp.into_iter() {
-        score += RPS::new(&player_2_shape).fight(&RPS::new(&player_1_shape));
-    }
-
-    score
+pub fn score(inp: &[Entry]) -> Result<i32, ParseError> {
+    puzzle_scheme(decode::SHAPES_SCHEME).score(inp)
 }
 
 /**
@@ -193,12 +162,88 @@ pub fn score(inp: &Vec<(String, String)>) -> i32 {
  * Following the Elf's instructions for the second column, what would your total
  * score be if everything goes exactly according to your strategy guide?
 */
-pub fn score_v2(inp: &Vec<(String, String)>) -> i32 {
-    inp.into_iter().fold(0, |score, (opp_shape, res_type)| {
-        let (my_shape, resolution) =
-            RPS::new(&opp_shape).shape_base_on_resolution(
-                Resolution::new(&res_type),
-            );
-        score + resolution.calc_score(&my_shape)
-    })
+pub fn score_v2(inp: &[Entry]) -> Result<i32, ParseError> {
+    puzzle_scheme(decode::OUTCOMES_SCHEME).score(inp)
+}
+
+pub struct RockPaperSissor;
+
+impl Solution for RockPaperSissor {
+    const INPUT_PATH: &'static str = "res/_2_rock_paper_sissor.txt";
+
+    /// The guide decoded with the schemes of both parts.
+    type Input = (Vec<Play>, Vec<Play>);
+    type Output = i32;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        let inp = get_inputs(raw)?;
+        Ok((
+            puzzle_scheme(decode::SHAPES_SCHEME).decode_all(&inp)?,
+            puzzle_scheme(decode::OUTCOMES_SCHEME).decode_all(&inp)?,
+        ))
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        puzzle_scheme(decode::SHAPES_SCHEME).total(&inp.0)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        puzzle_scheme(decode::OUTCOMES_SCHEME).total(&inp.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    pub(super) const EXAMPLE: &str = "\
+A Y
+B X
+C Z
+";
+
+    #[test]
+    fn part1_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(score(&inp), Ok(15));
+    }
+
+    #[test]
+    fn part2_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(score_v2(&inp), Ok(12));
+    }
+
+    #[test]
+    fn reject_unknown_tokens() {
+        let err = |raw| RockPaperSissor::parse(raw).unwrap_err().to_string();
+
+        assert_eq!(
+            err("A Y\nB X\nD Z\n"),
+            "day 2 input, line 3, column 1: expected A, B or C, found `D`"
+        );
+        assert_eq!(
+            err("A Y\nB  W\n"),
+            "day 2 input, line 2, column 4: expected X, Y or Z, found `W`"
+        );
+        assert_eq!(
+            err("A Y\nB\n"),
+            "day 2 input, line 2, column 2: expected the second column, found end of line"
+        );
+        assert_eq!(
+            err("A Y Z\n"),
+            "day 2 input, line 1, column 5: expected end of line, found `Z`"
+        );
+
+        // Only a scheme knows which tokens mean something
+        let inp = get_inputs("A Y\nD W\n").unwrap();
+        assert_eq!(inp[1].1.text, "W");
+        assert!(score(&inp).is_err());
+    }
+
+    #[test]
+    fn real_input() {
+        assert_eq!(solve_real_input::<RockPaperSissor>(), (9177, 12111));
+    }
 }
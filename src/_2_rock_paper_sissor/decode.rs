@@ -0,0 +1,301 @@
+use super::engine::{Game, Resolution, RPS};
+use super::Entry;
+use crate::error::ParseError;
+
+/// Part 1's reading of the guide: both columns are shapes.
+pub const SHAPES_SCHEME: &str = "\
+opponent A=Rock B=Paper C=Scissors
+response X=Rock Y=Paper Z=Scissors
+";
+
+/// Part 2's reading of the guide: the second column is how the round ends.
+pub const OUTCOMES_SCHEME: &str = "\
+opponent A=Rock B=Paper C=Scissors
+response X=loss Y=draw Z=win
+";
+
+/// A decoded line of the guide: its number, the opponent's shape and ours.
+pub type Play = (usize, RPS, RPS);
+
+/// What a token of the second column asks for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    Shape(RPS),
+    Outcome(Resolution),
+}
+
+/// How the two columns of a strategy guide translate into shapes, one line
+/// per column of `TOKEN=MEANING` pairs:
+///
+/// ```text
+/// opponent A=Rock B=Paper C=Scissors
+/// response X=loss Y=draw Z=Scissors
+/// ```
+///
+/// The opponent's tokens are shapes of the game, the response's are shapes or
+/// `loss`, `draw` and `win`.
+#[derive(Debug, Clone)]
+pub struct Scheme {
+    game: Game,
+    opponent: Vec<(String, RPS)>,
+    response: Vec<(String, Column)>,
+}
+
+impl Scheme {
+    pub fn new(game: &Game, raw: &str) -> Result<Scheme, String> {
+        let mut opponent = None;
+        let mut response = None;
+
+        for (idx, line) in raw.lines().enumerate() {
+            let mut fields = line.split_whitespace();
+            let Some(column) = fields.next() else {
+                continue;
+            };
+            let err = |msg: String| format!("line {}: {msg}", idx + 1);
+
+            let mut mappings: Vec<(String, Column)> = vec![];
+            for field in fields {
+                let (token, meaning) = field
+                    .split_once('=')
+                    .ok_or_else(|| err(format!("expected `TOKEN=MEANING`, found `{field}`")))?;
+                if mappings.iter().any(|(known, _)| known == token) {
+                    return Err(err(format!("token `{token}` is mapped twice")));
+                }
+
+                let meaning = match meaning {
+                    "loss" => Column::Outcome(Resolution::Loss),
+                    "draw" => Column::Outcome(Resolution::Draw),
+                    "win" => Column::Outcome(Resolution::Win),
+                    shape => Column::Shape(
+                        game.shape(shape)
+                            .ok_or_else(|| err(format!("unknown shape `{shape}`")))?,
+                    ),
+                };
+                mappings.push((String::from(token), meaning));
+            }
+
+            match column {
+                "opponent" if opponent.is_none() => {
+                    let shapes = mappings.into_iter().map(|(token, meaning)| match meaning {
+                        Column::Shape(shape) => Ok((token, shape)),
+                        Column::Outcome(_) => {
+                            Err(err(format!("the opponent's `{token}` must be a shape")))
+                        }
+                    });
+                    opponent = Some(shapes.collect::<Result<Vec<_>, String>>()?);
+                }
+                "response" if response.is_none() => response = Some(mappings),
+                "opponent" | "response" => {
+                    return Err(err(format!("column `{column}` is described twice")))
+                }
+                other => {
+                    return Err(err(format!(
+                        "expected `opponent` or `response`, found `{other}`"
+                    )))
+                }
+            }
+        }
+
+        Ok(Scheme {
+            game: game.clone(),
+            opponent: opponent.ok_or("missing the `opponent` column")?,
+            response: response.ok_or("missing the `response` column")?,
+        })
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// The opponent's shape for a token of the first column.
+    pub fn opponent(&self, token: &str) -> Option<RPS> {
+        self.opponent
+            .iter()
+            .find(|(known, _)| known == token)
+            .map(|(_, shape)| *shape)
+    }
+
+    /// What a token of the second column asks for.
+    pub fn response(&self, token: &str) -> Option<Column> {
+        self.response
+            .iter()
+            .find(|(known, _)| known == token)
+            .map(|(_, column)| *column)
+    }
+
+    /// The opponent's shape and the one to play for a line of the guide, or
+    /// `None` when a token has no meaning in this scheme.
+    pub fn decode(&self, opponent: &str, response: &str) -> Option<(RPS, RPS)> {
+        let theirs = self.opponent(opponent)?;
+        let mine = match self.response(response)? {
+            Column::Shape(shape) => shape,
+            Column::Outcome(resolution) => theirs.shape_base_on_resolution(resolution),
+        };
+
+        Some((theirs, mine))
+    }
+
+    /// Like `decode`, but points at the token that has no meaning in this
+    /// scheme.
+    pub fn decode_entry(&self, (opponent, response): &Entry) -> Result<Play, ParseError> {
+        if self.opponent(&opponent.text).is_none() {
+            let tokens = self.opponent.iter().map(|(token, _)| token.as_str());
+            return Err(opponent.unexpected(&one_of(tokens)));
+        }
+
+        let (theirs, mine) = self.decode(&opponent.text, &response.text).ok_or_else(|| {
+            let tokens = self.response.iter().map(|(token, _)| token.as_str());
+            response.unexpected(&one_of(tokens))
+        })?;
+
+        Ok((opponent.line, theirs, mine))
+    }
+
+    /// Every line of the guide, failing on the first one that does not decode.
+    pub fn decode_all(&self, inp: &[Entry]) -> Result<Vec<Play>, ParseError> {
+        inp.iter().map(|entry| self.decode_entry(entry)).collect()
+    }
+
+    /// Every line of the guide that decodes, and the errors of the lines
+    /// skipped instead.
+    pub fn decode_lenient(&self, inp: &[Entry]) -> (Vec<Play>, Vec<ParseError>) {
+        let mut plays = vec![];
+        let mut skipped = vec![];
+
+        for entry in inp.iter() {
+            match self.decode_entry(entry) {
+                Ok(play) => plays.push(play),
+                Err(err) => skipped.push(err),
+            }
+        }
+
+        (plays, skipped)
+    }
+
+    /// Total score of the decoded `plays`.
+    pub fn total(&self, plays: &[Play]) -> i32 {
+        plays
+            .iter()
+            .map(|(_, theirs, mine)| self.game.score(mine, theirs))
+            .sum()
+    }
+
+    /// Total score of following the guide, which must decode throughout.
+    pub fn score(&self, inp: &[Entry]) -> Result<i32, ParseError> {
+        Ok(self.total(&self.decode_all(inp)?))
+    }
+
+    /// Total score of the lines that decode, and the errors of those skipped.
+    pub fn score_lenient(&self, inp: &[Entry]) -> (i32, Vec<ParseError>) {
+        let (plays, skipped) = self.decode_lenient(inp);
+        (self.total(&plays), skipped)
+    }
+}
+
+/// `A`, `A or B`, `A, B or C` and so on.
+fn one_of<'a>(tokens: impl Iterator<Item = &'a str>) -> String {
+    let tokens: Vec<&str> = tokens.collect();
+    match tokens.split_last() {
+        Some((last, [])) => String::from(*last),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::from("nothing"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{get_inputs, tests::EXAMPLE};
+    use super::*;
+
+    #[test]
+    fn puzzle_schemes() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        let game = Game::rock_paper_scissors();
+
+        assert_eq!(
+            Scheme::new(&game, SHAPES_SCHEME).unwrap().score(&inp),
+            Ok(15)
+        );
+        assert_eq!(
+            Scheme::new(&game, OUTCOMES_SCHEME).unwrap().score(&inp),
+            Ok(12)
+        );
+    }
+
+    #[test]
+    fn alternative_schemes() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+
+        let mixed = "\
+opponent C=Rock B=Paper A=Scissors
+response X=win Y=Paper Z=draw
+";
+        let scheme = Scheme::new(&Game::rock_paper_scissors(), mixed).unwrap();
+        // Scissors-Paper loses, Paper-Win is Scissors, Rock-Draw is Rock
+        assert_eq!(scheme.score(&inp), Ok(2 + 9 + 4));
+
+        let spock = "\
+opponent A=Rock B=Paper C=Spock
+response X=Lizard Y=Paper Z=Spock
+";
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let scheme = Scheme::new(&game, spock).unwrap();
+        // Lizard eats Paper, Spock draws with Spock
+        assert_eq!(scheme.score(&inp), Ok(8 + 11 + 7));
+        assert_eq!(scheme.decode("D", "X"), None);
+
+        // Tokens the puzzle's schemes know nothing about
+        let digits = "\
+opponent 1=Rock 2=Paper 3=Scissors
+response L=loss D=draw W=win
+";
+        let scheme = Scheme::new(&Game::rock_paper_scissors(), digits).unwrap();
+        let inp = get_inputs("1 D\n2 L\n3 W\n").unwrap();
+        assert_eq!(scheme.score(&inp), Ok(4 + 1 + 7));
+    }
+
+    #[test]
+    fn reject_unknown_tokens() {
+        let scheme = Scheme::new(&Game::rock_paper_scissors(), OUTCOMES_SCHEME).unwrap();
+        let inp = get_inputs("A Y\nD X\nC  W\n").unwrap();
+
+        assert_eq!(
+            scheme.score(&inp).map_err(|err| err.to_string()),
+            Err(String::from(
+                "day 2 input, line 2, column 1: expected A, B or C, found `D`"
+            ))
+        );
+
+        let (score, skipped) = scheme.score_lenient(&inp);
+        assert_eq!(score, 4);
+        let skipped: Vec<String> = skipped.iter().map(|err| err.to_string()).collect();
+        assert_eq!(
+            skipped,
+            [
+                "day 2 input, line 2, column 1: expected A, B or C, found `D`",
+                "day 2 input, line 3, column 4: expected X, Y or Z, found `W`"
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_schemes() {
+        let game = Game::rock_paper_scissors();
+        let err = |raw| Scheme::new(&game, raw).unwrap_err();
+
+        assert_eq!(
+            err("opponent A=Rock\nresponse X=Rock X=Paper"),
+            "line 2: token `X` is mapped twice"
+        );
+        assert_eq!(
+            err("opponent A=win\nresponse X=Rock"),
+            "line 1: the opponent's `A` must be a shape"
+        );
+        assert_eq!(err("opponent A=Stone\n"), "line 1: unknown shape `Stone`");
+        assert_eq!(err("opponent A=Rock\n"), "missing the `response` column");
+        assert_eq!(
+            err("player A=Rock\n"),
+            "line 1: expected `opponent` or `response`, found `player`"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+
+/// How a round ends for the player whose shape is looked at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Resolution {
+    pub fn calc_score(&self, played_shape: &RPS, scoring: &Scoring) -> i32 {
+        let outcome = match self {
+            Resolution::Loss => scoring.loss,
+            Resolution::Draw => scoring.draw,
+            Resolution::Win => scoring.win,
+        };
+
+        outcome + scoring.shapes[played_shape.index]
+    }
+
+    /// The same round seen from the other player.
+    pub fn reverse(&self) -> Resolution {
+        match self {
+            Resolution::Win => Resolution::Loss,
+            Resolution::Draw => Resolution::Draw,
+            Resolution::Loss => Resolution::Win,
+        }
+    }
+}
+
+/// Named as in the outcome columns of a `Scheme`.
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Resolution::Win => write!(f, "win"),
+            Resolution::Draw => write!(f, "draw"),
+            Resolution::Loss => write!(f, "loss"),
+        }
+    }
+}
+
+/// A shape of a cyclic game: its position in the cycle of `shapes` shapes.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RPS {
+    pub index: usize,
+    pub shapes: usize,
+}
+
+impl RPS {
+    /// A shape beats the ones an odd number of steps behind it in the cycle,
+    /// which is half of the others when there is an odd number of shapes.
+    pub fn fight_resolution(&self, other: &Self) -> Resolution {
+        let steps = (self.index + self.shapes - other.index) % self.shapes;
+
+        if steps == 0 {
+            Resolution::Draw
+        } else if steps % 2 == 1 {
+            Resolution::Win
+        } else {
+            Resolution::Loss
+        }
+    }
+
+    /// Every shape of the cycle, starting from the first.
+    pub fn all(shapes: usize) -> impl Iterator<Item = RPS> {
+        (0..shapes).map(move |index| RPS { index, shapes })
+    }
+
+    /// The shape to play against `self` for the round to end in `resolution`.
+    pub fn shape_base_on_resolution(&self, resolution: Resolution) -> RPS {
+        RPS::all(self.shapes)
+            .find(|shape| shape.fight_resolution(self) == resolution)
+            .expect("every shape draws, beats and loses to some shape")
+    }
+}
+
+/// Points of a round: the played shape's plus the outcome's.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scoring {
+    /// Points for playing each shape, in cycle order.
+    pub shapes: Vec<i32>,
+    pub loss: i32,
+    pub draw: i32,
+    pub win: i32,
+}
+
+impl Scoring {
+    /// The puzzle's scoring: 1 point for the first shape, 2 for the second and
+    /// so on, plus 0 for a loss, 3 for a draw and 6 for a win.
+    pub fn classic(shapes: usize) -> Scoring {
+        Scoring {
+            shapes: (1..=shapes as i32).collect(),
+            loss: 0,
+            draw: 3,
+            win: 6,
+        }
+    }
+}
+
+/// A cyclic game such as Rock-Paper-Scissors: each shape beats the shapes an
+/// odd number of steps behind it in the cycle of names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    names: Vec<String>,
+    scoring: Scoring,
+}
+
+impl Game {
+    pub fn new(names: &[&str], scoring: Scoring) -> Result<Game, String> {
+        if names.len() < 3 || names.len().is_multiple_of(2) {
+            return Err(format!(
+                "a cyclic game needs an odd number of shapes, at least 3, got {}",
+                names.len()
+            ));
+        }
+
+        for (idx, name) in names.iter().enumerate() {
+            if names[..idx].contains(name) {
+                return Err(format!("shape `{name}` appears twice"));
+            }
+        }
+
+        if scoring.shapes.len() != names.len() {
+            return Err(format!(
+                "scoring has points for {} shapes, the game has {}",
+                scoring.shapes.len(),
+                names.len()
+            ));
+        }
+
+        Ok(Game {
+            names: names.iter().map(|name| String::from(*name)).collect(),
+            scoring,
+        })
+    }
+
+    pub fn rock_paper_scissors() -> Game {
+        Game::new(&["Rock", "Paper", "Scissors"], Scoring::classic(3)).unwrap()
+    }
+
+    /// Scissors cuts paper, paper covers rock, rock crushes lizard, lizard
+    /// poisons Spock, Spock smashes scissors, scissors decapitates lizard,
+    /// lizard eats paper, paper disproves Spock, Spock vaporizes rock and rock
+    /// crushes scissors.
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        let names = ["Rock", "Paper", "Scissors", "Spock", "Lizard"];
+        Game::new(&names, Scoring::classic(5)).unwrap()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = RPS> {
+        RPS::all(self.names.len())
+    }
+
+    pub fn shape(&self, name: &str) -> Option<RPS> {
+        let index = self.names.iter().position(|each| each == name)?;
+        Some(RPS {
+            index,
+            shapes: self.names.len(),
+        })
+    }
+
+    pub fn name(&self, shape: &RPS) -> &str {
+        &self.names[shape.index]
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    /// Points for playing `mine` against `theirs`.
+    pub fn score(&self, mine: &RPS, theirs: &RPS) -> i32 {
+        mine.fight_resolution(theirs)
+            .calc_score(mine, &self.scoring)
+    }
+
+    /// The shapes `shape` beats.
+    pub fn beaten_by(&self, shape: &RPS) -> Vec<RPS> {
+        self.shapes()
+            .filter(|other| shape.fight_resolution(other) == Resolution::Win)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beats_relation_follows_the_cycle() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let shape = |name| game.shape(name).unwrap();
+        let names = |shapes: Vec<RPS>| -> Vec<String> {
+            shapes
+                .iter()
+                .map(|shape| String::from(game.name(shape)))
+                .collect()
+        };
+
+        assert_eq!(
+            names(game.beaten_by(&shape("Rock"))),
+            ["Scissors", "Lizard"]
+        );
+        assert_eq!(names(game.beaten_by(&shape("Paper"))), ["Rock", "Spock"]);
+        assert_eq!(
+            names(game.beaten_by(&shape("Scissors"))),
+            ["Paper", "Lizard"]
+        );
+        assert_eq!(names(game.beaten_by(&shape("Spock"))), ["Rock", "Scissors"]);
+        assert_eq!(names(game.beaten_by(&shape("Lizard"))), ["Paper", "Spock"]);
+
+        for mine in game.shapes() {
+            for theirs in game.shapes() {
+                assert_eq!(
+                    mine.fight_resolution(&theirs),
+                    theirs.fight_resolution(&mine).reverse()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn score_rounds() {
+        let game = Game::rock_paper_scissors();
+        let (rock, paper) = (game.shape("Rock").unwrap(), game.shape("Paper").unwrap());
+        assert_eq!(game.score(&paper, &rock), 8);
+        assert_eq!(game.score(&rock, &paper), 1);
+        assert_eq!(rock.shape_base_on_resolution(Resolution::Win), paper);
+
+        let scoring = Scoring {
+            shapes: vec![0, 0, 0],
+            loss: -1,
+            draw: 0,
+            win: 1,
+        };
+        let game = Game::new(&["Rock", "Paper", "Scissors"], scoring).unwrap();
+        assert_eq!(game.score(&rock, &paper), -1);
+    }
+
+    #[test]
+    fn reject_invalid_games() {
+        assert!(Game::new(&["Rock", "Paper"], Scoring::classic(2)).is_err());
+        assert!(Game::new(&["Rock", "Paper", "Rock"], Scoring::classic(3)).is_err());
+        assert!(Game::new(&["Rock", "Paper", "Scissors"], Scoring::classic(5)).is_err());
+    }
+}
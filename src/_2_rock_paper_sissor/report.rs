@@ -0,0 +1,312 @@
+use std::fmt::Write;
+
+use super::decode::{Play, Scheme};
+use super::engine::{Game, Resolution, RPS};
+use super::Entry;
+use crate::error::ParseError;
+
+/// One round of the guide with its points split up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round {
+    /// Line of the guide, counted from 1.
+    pub line: usize,
+    pub theirs: RPS,
+    pub mine: RPS,
+    pub resolution: Resolution,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+}
+
+impl Round {
+    pub fn points(&self) -> i32 {
+        self.shape_points + self.outcome_points
+    }
+}
+
+/// How the rounds ended for one of our shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tally {
+    pub shape: RPS,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// Every round of following a guide, for auditing where its score comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    game: Game,
+    pub rounds: Vec<Round>,
+}
+
+impl Report {
+    /// Fails on the first round `scheme` cannot decode.
+    pub fn new(scheme: &Scheme, inp: &[Entry]) -> Result<Report, ParseError> {
+        Ok(Report::from_rounds(scheme.game(), scheme.decode_all(inp)?))
+    }
+
+    /// Leaves out the rounds `scheme` cannot decode, returning their errors.
+    pub fn lenient(scheme: &Scheme, inp: &[Entry]) -> (Report, Vec<ParseError>) {
+        let (rounds, skipped) = scheme.decode_lenient(inp);
+        (Report::from_rounds(scheme.game(), rounds), skipped)
+    }
+
+    fn from_rounds(game: &Game, rounds: Vec<Play>) -> Report {
+        let scoring = game.scoring();
+
+        let rounds = rounds
+            .into_iter()
+            .map(|(line, theirs, mine)| {
+                let resolution = mine.fight_resolution(&theirs);
+                let shape_points = scoring.shapes[mine.index];
+
+                Round {
+                    line,
+                    theirs,
+                    mine,
+                    resolution,
+                    shape_points,
+                    outcome_points: resolution.calc_score(&mine, scoring) - shape_points,
+                }
+            })
+            .collect();
+
+        Report {
+            game: game.clone(),
+            rounds,
+        }
+    }
+
+    pub fn total(&self) -> i32 {
+        self.rounds.iter().map(|round| round.points()).sum()
+    }
+
+    /// Wins, draws and losses of each of our shapes, in cycle order.
+    pub fn tallies(&self) -> Vec<Tally> {
+        let mut tallies: Vec<Tally> = self
+            .game
+            .shapes()
+            .map(|shape| Tally {
+                shape,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+            })
+            .collect();
+
+        for round in self.rounds.iter() {
+            let tally = &mut tallies[round.mine.index];
+            match round.resolution {
+                Resolution::Win => tally.wins += 1,
+                Resolution::Draw => tally.draws += 1,
+                Resolution::Loss => tally.losses += 1,
+            }
+        }
+
+        tallies
+    }
+
+    /// The rounds, their total and the tallies as aligned columns.
+    pub fn table(&self) -> String {
+        let width = self
+            .game
+            .shapes()
+            .map(|shape| self.game.name(&shape).len())
+            .max()
+            .unwrap_or(0)
+            .max("opponent".len());
+
+        let mut table = format!(
+            "{:>5}  {:<width$}  {:<width$}  {:<7}  {:>9}  {:>11}  {:>6}\n",
+            "line", "opponent", "response", "outcome", "shape pts", "outcome pts", "points"
+        );
+        for round in self.rounds.iter() {
+            let _ = writeln!(
+                table,
+                "{:>5}  {:<width$}  {:<width$}  {:<7}  {:>9}  {:>11}  {:>6}",
+                round.line,
+                self.game.name(&round.theirs),
+                self.game.name(&round.mine),
+                round.resolution.to_string(),
+                round.shape_points,
+                round.outcome_points,
+                round.points()
+            );
+        }
+        let _ = writeln!(table, "total: {}", self.total());
+
+        let _ = writeln!(
+            table,
+            "\n{:<width$}  {:>5}  {:>5}  {:>6}",
+            "shape", "wins", "draws", "losses"
+        );
+        for tally in self.tallies() {
+            let _ = writeln!(
+                table,
+                "{:<width$}  {:>5}  {:>5}  {:>6}",
+                self.game.name(&tally.shape),
+                tally.wins,
+                tally.draws,
+                tally.losses
+            );
+        }
+
+        table
+    }
+
+    /// The rounds as CSV, followed after a blank line by the tallies as CSV.
+    pub fn csv(&self) -> String {
+        let mut csv =
+            String::from("line,opponent,response,outcome,shape_points,outcome_points,points\n");
+        for round in self.rounds.iter() {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                round.line,
+                csv_field(self.game.name(&round.theirs)),
+                csv_field(self.game.name(&round.mine)),
+                round.resolution,
+                round.shape_points,
+                round.outcome_points,
+                round.points()
+            );
+        }
+
+        csv.push_str("\nshape,wins,draws,losses\n");
+        for tally in self.tallies() {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(self.game.name(&tally.shape)),
+                tally.wins,
+                tally.draws,
+                tally.losses
+            );
+        }
+
+        csv
+    }
+}
+
+/// `field` as a CSV field: quoted, with its quotes doubled, when it holds a
+/// comma, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        decode::{OUTCOMES_SCHEME, SHAPES_SCHEME},
+        engine::Scoring,
+        get_inputs,
+        tests::EXAMPLE,
+    };
+    use super::*;
+
+    fn report(scheme: &str) -> Report {
+        let scheme = Scheme::new(&Game::rock_paper_scissors(), scheme).unwrap();
+        Report::new(&scheme, &get_inputs(EXAMPLE).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn report_example() {
+        let shapes = report(SHAPES_SCHEME);
+        assert_eq!(shapes.total(), 15);
+        assert_eq!(
+            shapes
+                .rounds
+                .iter()
+                .map(|round| (round.shape_points, round.outcome_points))
+                .collect::<Vec<_>>(),
+            [(2, 6), (1, 0), (3, 3)]
+        );
+
+        assert_eq!(
+            shapes.csv(),
+            "\
+line,opponent,response,outcome,shape_points,outcome_points,points
+1,Rock,Paper,win,2,6,8
+2,Paper,Rock,loss,1,0,1
+3,Scissors,Scissors,draw,3,3,6
+
+shape,wins,draws,losses
+Rock,0,0,1
+Paper,1,0,0
+Scissors,0,1,0
+"
+        );
+
+        let outcomes = report(OUTCOMES_SCHEME);
+        assert_eq!(outcomes.total(), 12);
+        assert_eq!(outcomes.tallies()[0].draws, 1);
+        assert_eq!(outcomes.tallies()[0].wins, 1);
+    }
+
+    #[test]
+    fn report_csv_quotes_names() {
+        let game = Game::new(&["Rock,big", "Pa\"per", "Scissors"], Scoring::classic(3)).unwrap();
+        let scheme = Scheme::new(
+            &game,
+            "opponent A=Rock,big B=Pa\"per C=Scissors\nresponse X=Rock,big Y=Pa\"per Z=Scissors",
+        )
+        .unwrap();
+        let csv = Report::new(&scheme, &get_inputs("A Y\n").unwrap())
+            .unwrap()
+            .csv();
+
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "line,opponent,response,outcome,shape_points,outcome_points,points",
+                "1,\"Rock,big\",\"Pa\"\"per\",win,2,6,8",
+                "",
+                "shape,wins,draws,losses",
+                "\"Rock,big\",0,0,0",
+                "\"Pa\"\"per\",1,0,0",
+                "Scissors,0,0,0",
+            ]
+        );
+    }
+
+    #[test]
+    fn report_table() {
+        let table = report(SHAPES_SCHEME).table();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            " line  opponent  response  outcome  shape pts  outcome pts  points"
+        );
+        assert_eq!(
+            lines[1],
+            "    1  Rock      Paper     win              2            6       8"
+        );
+        assert_eq!(lines[4], "total: 15");
+        assert_eq!(lines[8], "Paper         1      0       0");
+    }
+
+    #[test]
+    fn report_skipped_rounds() {
+        let scheme = Scheme::new(&Game::rock_paper_scissors(), SHAPES_SCHEME).unwrap();
+        let inp = get_inputs("A Y\nA Q\nB X\nC Z\n").unwrap();
+
+        assert!(Report::new(&scheme, &inp).is_err());
+        let (report, skipped) = Report::lenient(&scheme, &inp);
+        assert_eq!(report.total(), 15);
+        assert_eq!(
+            report
+                .rounds
+                .iter()
+                .map(|round| round.line)
+                .collect::<Vec<_>>(),
+            [1, 3, 4]
+        );
+        assert_eq!(skipped.len(), 1);
+        assert_eq!((skipped[0].line, skipped[0].column), (2, 3));
+    }
+}
@@ -0,0 +1,279 @@
+use super::engine::{Game, Resolution, RPS};
+
+/// Limits on how obviously the responses may exploit the opponent.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Constraints {
+    /// Win at most this percent of the rounds, rounded down.
+    pub max_win_percent: Option<u32>,
+    /// Play the same shape at most this many rounds in a row.
+    pub max_repeats: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    /// Our shape for each round.
+    pub shapes: Vec<RPS>,
+    pub score: i32,
+    pub wins: usize,
+}
+
+/// Finds the responses to `opponent`'s shapes with the highest total score
+/// that still meet `constraints`.
+///
+/// Rounds are walked in order, keeping the best score of every reachable
+/// (wins so far, last shape, times in a row) state, so the work grows with
+/// rounds times allowed wins times shapes times allowed repeats. Only the
+/// states starting a run need remembering where they came from, so the memory
+/// grows with rounds times allowed wins times shapes.
+pub fn solve(game: &Game, opponent: &[RPS], constraints: &Constraints) -> Result<Plan, String> {
+    if constraints.max_repeats == Some(0) && !opponent.is_empty() {
+        return Err(String::from(
+            "a shape must be allowed at least once in a row",
+        ));
+    }
+
+    let shapes: Vec<RPS> = game.shapes().collect();
+    // Only the constrained dimensions are tracked, the others stay at size 1
+    let max_wins = constraints
+        .max_win_percent
+        .map(|percent| (percent.min(100) as usize * opponent.len()) / 100);
+    let wins_dim = max_wins.map_or(1, |max_wins| max_wins + 1);
+    let run_dim = constraints
+        .max_repeats
+        .map_or(1, |max| max.min(opponent.len().max(1)));
+    let states = wins_dim * shapes.len() * run_dim;
+    let state =
+        |wins: usize, shape: usize, run: usize| (wins * shapes.len() + shape) * run_dim + run;
+
+    // Points and whether it is a win, for every round and shape
+    let rounds: Vec<Vec<(i32, bool)>> = opponent
+        .iter()
+        .map(|theirs| {
+            shapes
+                .iter()
+                .map(|mine| {
+                    let resolution = mine.fight_resolution(theirs);
+                    (
+                        resolution.calc_score(mine, game.scoring()),
+                        resolution == Resolution::Win,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    // Best (score, wins) of every state after the latest round
+    let mut best: Vec<Option<(i32, usize)>> = vec![None; states];
+    // Shape and run, as `shape * run_dim + run`, that each round's states with
+    // a run of 0 were reached from, `u32::MAX` in the first round. The others can only follow the same shape
+    // with a run one shorter.
+    let mut came_from: Vec<Vec<u32>> = vec![];
+    let mut next: Vec<Option<(i32, usize)>> = vec![None; states];
+
+    for (round, points) in rounds.iter().enumerate() {
+        next.fill(None);
+        let mut from = vec![u32::MAX; wins_dim * shapes.len()];
+
+        // The first round starts from nothing
+        let prevs = (round == 0).then_some(None).into_iter().chain(
+            (0..states)
+                .filter(|idx| round > 0 && best[*idx].is_some())
+                .map(Some),
+        );
+
+        for prev in prevs {
+            let (score, wins) = prev.map_or((0, 0), |prev| best[prev].unwrap());
+            // The previous shape when its run may go on, and the next run
+            let (run_shape, next_run) = match prev {
+                Some(prev) if constraints.max_repeats.is_some() => {
+                    (Some((prev / run_dim) % shapes.len()), prev % run_dim + 1)
+                }
+                _ => (None, 0),
+            };
+            let packed = prev.map_or(u32::MAX, |prev| (prev % (shapes.len() * run_dim)) as u32);
+
+            for (shape, (points, won)) in points.iter().enumerate() {
+                let wins = wins + usize::from(*won);
+                if max_wins.is_some_and(|max_wins| wins > max_wins) {
+                    continue;
+                }
+
+                let run = if run_shape == Some(shape) {
+                    next_run
+                } else {
+                    0
+                };
+                if run >= run_dim {
+                    continue;
+                }
+
+                let idx = state(if max_wins.is_some() { wins } else { 0 }, shape, run);
+                let score = score + points;
+                if next[idx].is_none_or(|(known, _)| score > known) {
+                    next[idx] = Some((score, wins));
+                    if run == 0 {
+                        // Without the wins, the round's outcome tells them apart
+                        from[idx / run_dim] = packed;
+                    }
+                }
+            }
+        }
+
+        std::mem::swap(&mut best, &mut next);
+        came_from.push(from);
+    }
+
+    let Some(last) = (0..states)
+        .filter(|idx| best[*idx].is_some())
+        .max_by_key(|idx| best[*idx])
+    else {
+        return Ok(Plan {
+            shapes: vec![],
+            score: 0,
+            wins: 0,
+        });
+    };
+
+    let mut plan_shapes = vec![];
+    let mut idx = last;
+    for (from, points) in came_from.iter().zip(rounds.iter()).rev() {
+        let (wins, shape, run) = (
+            idx / run_dim / shapes.len(),
+            (idx / run_dim) % shapes.len(),
+            idx % run_dim,
+        );
+        plan_shapes.push(shapes[shape]);
+
+        let wins = wins - usize::from(max_wins.is_some() && points[shape].1);
+        let (shape, run) = match (run, from[idx / run_dim]) {
+            (0, u32::MAX) => break,
+            (0, prev) => (prev as usize / run_dim, prev as usize % run_dim),
+            _ => (shape, run - 1),
+        };
+        idx = state(wins, shape, run);
+    }
+    plan_shapes.reverse();
+
+    let (score, wins) = best[last].unwrap();
+    Ok(Plan {
+        shapes: plan_shapes,
+        score,
+        wins,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rocks(rounds: usize) -> Vec<RPS> {
+        vec![Game::rock_paper_scissors().shape("Rock").unwrap(); rounds]
+    }
+
+    fn longest_run(shapes: &[RPS]) -> usize {
+        shapes
+            .chunk_by(|a, b| a == b)
+            .map(|run| run.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn solve_example() {
+        let game = Game::rock_paper_scissors();
+        let opponent: Vec<RPS> = game.shapes().collect();
+
+        let free = solve(&game, &opponent, &Constraints::default()).unwrap();
+        assert_eq!((free.score, free.wins), (24, 3));
+
+        // Winning the first or second round gains the most over a draw
+        let constraints = Constraints {
+            max_win_percent: Some(34),
+            ..Constraints::default()
+        };
+        let plan = solve(&game, &opponent, &constraints).unwrap();
+        assert_eq!((plan.score, plan.wins), (19, 1));
+    }
+
+    #[test]
+    fn solve_with_repeat_limit() {
+        let game = Game::rock_paper_scissors();
+
+        let constraints = Constraints {
+            max_repeats: Some(2),
+            ..Constraints::default()
+        };
+        let plan = solve(&game, &rocks(4), &constraints).unwrap();
+        assert_eq!(plan.score, 8 + 8 + 4 + 8);
+        assert!(longest_run(&plan.shapes) <= 2);
+
+        let constraints = Constraints {
+            max_win_percent: Some(50),
+            max_repeats: Some(1),
+        };
+        let plan = solve(&game, &rocks(4), &constraints).unwrap();
+        assert_eq!((plan.score, plan.wins), (24, 2));
+        assert_eq!(longest_run(&plan.shapes), 1);
+
+        let constraints = Constraints {
+            max_repeats: Some(0),
+            ..Constraints::default()
+        };
+        assert!(solve(&game, &rocks(4), &constraints).is_err());
+    }
+
+    #[test]
+    fn solve_matches_brute_force() {
+        let game = Game::rock_paper_scissors();
+        let shapes: Vec<RPS> = game.shapes().collect();
+        let opponent: Vec<RPS> = [0, 0, 1, 2, 2, 2, 1]
+            .iter()
+            .map(|idx| shapes[*idx])
+            .collect();
+
+        for max_win_percent in [None, Some(0), Some(30), Some(60)] {
+            for max_repeats in [None, Some(1), Some(2)] {
+                let constraints = Constraints {
+                    max_win_percent,
+                    max_repeats,
+                };
+                let max_wins =
+                    max_win_percent.map(|percent| percent as usize * opponent.len() / 100);
+
+                let mut best = None;
+                for choice in 0..shapes.len().pow(opponent.len() as u32) {
+                    let mine: Vec<RPS> = (0..opponent.len())
+                        .map(|round| shapes[choice / shapes.len().pow(round as u32) % shapes.len()])
+                        .collect();
+                    let wins = mine
+                        .iter()
+                        .zip(opponent.iter())
+                        .filter(|(mine, theirs)| game.score(mine, theirs) > 6)
+                        .count();
+                    if max_wins.is_some_and(|max_wins| wins > max_wins)
+                        || max_repeats.is_some_and(|max| longest_run(&mine) > max)
+                    {
+                        continue;
+                    }
+
+                    let score = mine
+                        .iter()
+                        .zip(opponent.iter())
+                        .map(|(mine, theirs)| game.score(mine, theirs))
+                        .sum();
+                    best = best.max(Some(score));
+                }
+
+                let plan = solve(&game, &opponent, &constraints).unwrap();
+                assert_eq!(Some(plan.score), best, "{constraints:?}");
+                let score: i32 = plan
+                    .shapes
+                    .iter()
+                    .zip(opponent.iter())
+                    .map(|(mine, theirs)| game.score(mine, theirs))
+                    .sum();
+                assert_eq!(score, plan.score, "{constraints:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::fmt;
+
+use super::decode::Scheme;
+use super::engine::{Game, Resolution, RPS};
+use super::Entry;
+
+/// A small seeded generator (splitmix64), so that a tournament can be replayed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    pub fn shape(&mut self, game: &Game) -> RPS {
+        let shapes: Vec<RPS> = game.shapes().collect();
+        shapes[self.below(shapes.len())]
+    }
+}
+
+/// A bot playing a match round after round.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Forgets everything seen, before a new match.
+    fn reset(&mut self) {}
+
+    fn play(&mut self, game: &Game, rng: &mut Rng) -> RPS;
+
+    /// Shows the round just played, `mine` being the shape `play` returned.
+    fn observe(&mut self, _mine: RPS, _theirs: RPS) {}
+}
+
+/// Plays the same shapes over and over, such as the responses of a guide.
+pub struct Fixed {
+    name: String,
+    shapes: Vec<RPS>,
+    next: usize,
+}
+
+impl Fixed {
+    pub fn new(name: &str, shapes: Vec<RPS>) -> Result<Fixed, String> {
+        if shapes.is_empty() {
+            return Err(format!("strategy `{name}` has no shape to play"));
+        }
+
+        Ok(Fixed {
+            name: String::from(name),
+            shapes,
+            next: 0,
+        })
+    }
+
+    /// Plays the shapes `scheme` decodes for the guide's responses.
+    pub fn from_guide(name: &str, scheme: &Scheme, inp: &[Entry]) -> Result<Fixed, String> {
+        let shapes = scheme
+            .decode_all(inp)
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(|(_, _, mine)| mine)
+            .collect();
+        Fixed::new(name, shapes)
+    }
+}
+
+impl Strategy for Fixed {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn reset(&mut self) {
+        self.next = 0;
+    }
+
+    fn play(&mut self, _game: &Game, _rng: &mut Rng) -> RPS {
+        let shape = self.shapes[self.next];
+        self.next = (self.next + 1) % self.shapes.len();
+        shape
+    }
+}
+
+/// Plays any shape.
+pub struct Random;
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        String::from("random")
+    }
+
+    fn play(&mut self, game: &Game, rng: &mut Rng) -> RPS {
+        rng.shape(game)
+    }
+}
+
+/// Beats the shape the opponent played the most so far.
+#[derive(Default)]
+pub struct Frequency {
+    counts: Vec<usize>,
+}
+
+impl Strategy for Frequency {
+    fn name(&self) -> String {
+        String::from("frequency")
+    }
+
+    fn reset(&mut self) {
+        self.counts.clear();
+    }
+
+    fn play(&mut self, game: &Game, rng: &mut Rng) -> RPS {
+        match most_likely(&self.counts, game) {
+            Some(theirs) => theirs.shape_base_on_resolution(Resolution::Win),
+            None => rng.shape(game),
+        }
+    }
+
+    fn observe(&mut self, _mine: RPS, theirs: RPS) {
+        self.counts.resize(theirs.shapes, 0);
+        self.counts[theirs.index] += 1;
+    }
+}
+
+/// Beats the shape the opponent most often followed their last shape with.
+#[derive(Default)]
+pub struct Markov {
+    /// How many times each shape came after each other shape.
+    transitions: Vec<Vec<usize>>,
+    last: Option<RPS>,
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        String::from("markov")
+    }
+
+    fn reset(&mut self) {
+        self.transitions.clear();
+        self.last = None;
+    }
+
+    fn play(&mut self, game: &Game, rng: &mut Rng) -> RPS {
+        let predicted = self
+            .last
+            .and_then(|last| most_likely(self.transitions.get(last.index)?, game));
+
+        match predicted {
+            Some(theirs) => theirs.shape_base_on_resolution(Resolution::Win),
+            None => rng.shape(game),
+        }
+    }
+
+    fn observe(&mut self, _mine: RPS, theirs: RPS) {
+        self.transitions
+            .resize(theirs.shapes, vec![0; theirs.shapes]);
+        if let Some(last) = self.last {
+            self.transitions[last.index][theirs.index] += 1;
+        }
+        self.last = Some(theirs);
+    }
+}
+
+/// Plays whatever the opponent played last.
+#[derive(Default)]
+pub struct CopyLast {
+    last: Option<RPS>,
+}
+
+impl Strategy for CopyLast {
+    fn name(&self) -> String {
+        String::from("copy-last")
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+
+    fn play(&mut self, game: &Game, rng: &mut Rng) -> RPS {
+        self.last.unwrap_or_else(|| rng.shape(game))
+    }
+
+    fn observe(&mut self, _mine: RPS, theirs: RPS) {
+        self.last = Some(theirs);
+    }
+}
+
+/// The most counted shape, the first one on ties, or `None` before any count.
+fn most_likely(counts: &[usize], game: &Game) -> Option<RPS> {
+    let (index, count) = counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, count)| (**count, std::cmp::Reverse(*index)))?;
+
+    game.shapes().nth(index).filter(|_| *count > 0)
+}
+
+/// Total points of both sides of a match.
+pub fn play_match(
+    game: &Game,
+    (first, second): (&mut (dyn Strategy + '_), &mut (dyn Strategy + '_)),
+    rounds: usize,
+    rng: &mut Rng,
+) -> (i32, i32) {
+    first.reset();
+    second.reset();
+
+    let mut points = (0, 0);
+    for _ in 0..rounds {
+        let (a, b) = (first.play(game, rng), second.play(game, rng));
+        points.0 += game.score(&a, &b);
+        points.1 += game.score(&b, &a);
+        first.observe(a, b);
+        second.observe(b, a);
+    }
+
+    points
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    /// Points of every round played.
+    pub points: i32,
+    /// Matches won, drawn and lost on points.
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// Standings from the most points to the least.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leaderboard(pub Vec<Standing>);
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:<16}  {:>8}  {:>3}  {:>3}  {:>3}",
+            "rank", "strategy", "points", "w", "d", "l"
+        )?;
+        for (idx, standing) in self.0.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<16}  {:>8}  {:>3}  {:>3}  {:>3}",
+                idx + 1,
+                standing.name,
+                standing.points,
+                standing.wins,
+                standing.draws,
+                standing.losses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays a match of `rounds` rounds between every two of `strategies`, with
+/// all the randomness drawn from `seed`.
+pub fn round_robin(
+    game: &Game,
+    strategies: &mut [Box<dyn Strategy>],
+    rounds: usize,
+    seed: u64,
+) -> Leaderboard {
+    let mut rng = Rng::new(seed);
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| Standing {
+            name: strategy.name(),
+            points: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        })
+        .collect();
+
+    for second in 1..strategies.len() {
+        for first in 0..second {
+            let (head, tail) = strategies.split_at_mut(second);
+            let players = (head[first].as_mut(), tail[0].as_mut());
+            let (a, b) = play_match(game, players, rounds, &mut rng);
+
+            standings[first].points += a;
+            standings[second].points += b;
+            match a.cmp(&b) {
+                std::cmp::Ordering::Greater => {
+                    standings[first].wins += 1;
+                    standings[second].losses += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    standings[first].draws += 1;
+                    standings[second].draws += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    standings[first].losses += 1;
+                    standings[second].wins += 1;
+                }
+            }
+        }
+    }
+
+    // Stable, so ties keep the order the strategies were entered in
+    standings.sort_by_key(|standing| std::cmp::Reverse(standing.points));
+    Leaderboard(standings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{decode::SHAPES_SCHEME, get_inputs, tests::EXAMPLE};
+    use super::*;
+
+    fn bots(game: &Game) -> Vec<Box<dyn Strategy>> {
+        let rock = game.shape("Rock").unwrap();
+        vec![
+            Box::new(Fixed::new("rock", vec![rock]).unwrap()),
+            Box::new(Random),
+            Box::new(Frequency::default()),
+            Box::new(Markov::default()),
+            Box::new(CopyLast::default()),
+        ]
+    }
+
+    #[test]
+    fn rng_is_seeded() {
+        let draws = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..8).map(|_| rng.below(3)).collect::<Vec<usize>>()
+        };
+
+        assert_eq!(draws(7), draws(7));
+        assert_ne!(draws(7), draws(8));
+        assert!(draws(7).iter().all(|draw| *draw < 3));
+    }
+
+    #[test]
+    fn strategies_exploit_patterns() {
+        let game = Game::rock_paper_scissors();
+        let shape = |name| game.shape(name).unwrap();
+        let mut rng = Rng::new(1);
+
+        // Always Paper after the first round against Rock
+        let mut rock = Fixed::new("rock", vec![shape("Rock")]).unwrap();
+        let mut frequency = Frequency::default();
+        let (a, b) = play_match(&game, (&mut rock, &mut frequency), 10, &mut rng);
+        assert!(b >= 9 * 8, "{a} {b}");
+
+        // Beats a cycle once it has seen each step of it
+        let cycle = vec![shape("Rock"), shape("Paper"), shape("Scissors")];
+        let mut cycle = Fixed::new("cycle", cycle).unwrap();
+        let mut markov = Markov::default();
+        let (_, b) = play_match(&game, (&mut cycle, &mut markov), 30, &mut rng);
+        assert!(b >= 26 * 6, "{b}");
+
+        let mut copy = CopyLast::default();
+        copy.observe(shape("Rock"), shape("Scissors"));
+        assert_eq!(copy.play(&game, &mut rng), shape("Scissors"));
+        copy.reset();
+        assert_eq!(copy.last, None);
+    }
+
+    #[test]
+    fn fixed_guide_strategy() {
+        let game = Game::rock_paper_scissors();
+        let scheme = Scheme::new(&game, SHAPES_SCHEME).unwrap();
+        let mut guide = Fixed::from_guide("guide", &scheme, &get_inputs(EXAMPLE).unwrap()).unwrap();
+        let mut rng = Rng::new(0);
+
+        let played: Vec<&str> = (0..4)
+            .map(|_| game.name(&guide.play(&game, &mut rng)))
+            .collect();
+        assert_eq!(played, ["Paper", "Rock", "Scissors", "Paper"]);
+        assert!(Fixed::from_guide("empty", &scheme, &[]).is_err());
+    }
+
+    #[test]
+    fn round_robin_leaderboard() {
+        let game = Game::rock_paper_scissors();
+
+        let board = round_robin(&game, &mut bots(&game), 100, 42);
+        assert_eq!(board, round_robin(&game, &mut bots(&game), 100, 42));
+        assert_eq!(board.0.len(), 5);
+        for standing in board.0.iter() {
+            assert_eq!(standing.wins + standing.draws + standing.losses, 4);
+        }
+        assert!(board
+            .0
+            .windows(2)
+            .all(|pair| pair[0].points >= pair[1].points));
+        // Everyone but the random bot can read a constant Rock
+        assert_eq!(board.0.last().unwrap().name, "rock");
+
+        let table = board.to_string();
+        assert!(table.starts_with("rank  strategy"), "{table}");
+        assert_eq!(table.lines().count(), 6);
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 3: Rucksack Reorganization ---
  * One Elf has the important job of loading all of the rucksacks with supplies
  * for the jungle journey. Unfortunately, that Elf didn't quite follow the
@@ -104,40 +104,110 @@
  * Find the item type that corresponds to the badges of each three-Elf group.
  * What is the sum of the priorities of those item types?
 */
-use std::fs;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+pub mod alphabet;
+
+pub use alphabet::Alphabet;
+
+const DAY: u8 = 3;
 
 #[derive(Debug)]
 struct Group(String, String, String);
 
-pub fn get_inputs() -> Vec<String> {
-    fs::read_to_string("res/_3_rucksack_reorg.txt")
-        .unwrap()
-        .split("\r\n")
-        .map(|each| String::from(each))
+pub fn get_inputs(raw: &str) -> Result<Vec<String>, ParseError> {
+    get_inputs_with(raw, &Alphabet::puzzle())
+}
+
+/// Reads one rucksack per line, holding only item types of `alphabet`.
+pub fn get_inputs_with(raw: &str, alphabet: &Alphabet) -> Result<Vec<String>, ParseError> {
+    lines(raw)
+        .into_iter()
+        .map(|line| {
+            let (_, rucksack) = line;
+            if let Some((at, chr)) = alphabet.find_unknown(rucksack) {
+                let item = &rucksack[at..at + chr.len_utf8()];
+                let expected = format!("an item type {}", alphabet.describe());
+                return Err(ParseError::at(DAY, line, item, &expected));
+            }
+            if rucksack.chars().count() % 2 != 0 {
+                return Err(ParseError::at(
+                    DAY,
+                    line,
+                    rucksack,
+                    "an even number of items to split between both compartments",
+                ));
+            }
+            Ok(String::from(rucksack))
+        })
         .collect()
 }
 
 // Part 1 sol
-pub fn reorg(inp: &Vec<String>) -> i32 {
-    inp.into_iter()
-        .map(|each| each.split_at(each.len() / 2))
-        .map(get_common_item)
-        .flatten()
-        .map(convert_to_priority)
+pub fn reorg(inp: &[String]) -> Result<i32, String> {
+    reorg_with(inp, &Alphabet::puzzle())
+}
+
+pub fn reorg_with(inp: &[String], alphabet: &Alphabet) -> Result<i32, String> {
+    check_items(inp, alphabet)?;
+    Ok(sum_misplaced(inp, alphabet))
+}
+
+/// Leaves out unknown item types, which `check_items` reports.
+fn sum_misplaced(inp: &[String], alphabet: &Alphabet) -> i32 {
+    inp.iter()
+        .map(|each| each.split_at(middle(each)))
+        .flat_map(|halves| get_common_item(halves, alphabet))
+        .filter_map(|chr| alphabet.priority(chr))
         .sum()
 }
 
 // Part 2 sol
-pub fn stick_sticker(inp: &Vec<String>) -> i32 {
+pub fn stick_sticker(inp: &[String]) -> Result<i32, String> {
+    stick_sticker_with(inp, &Alphabet::puzzle())
+}
+
+pub fn stick_sticker_with(inp: &[String], alphabet: &Alphabet) -> Result<i32, String> {
+    check_items(inp, alphabet)?;
+    Ok(sum_badges(inp, alphabet))
+}
+
+/// Leaves out unknown item types, which `check_items` reports.
+fn sum_badges(inp: &[String], alphabet: &Alphabet) -> i32 {
     group_into_threes(inp)
         .into_iter()
-        .map(get_common_item_in_group)
-        .flatten()
-        .map(convert_to_priority)
+        .flat_map(|group| get_common_item_in_group(group, alphabet))
+        .filter_map(|chr| alphabet.priority(chr))
         .sum()
 }
 
-fn group_into_threes(rucksacks: &Vec<String>) -> Vec<Group> {
+/// Makes sure every item is one of `alphabet`.
+fn check_items(inp: &[String], alphabet: &Alphabet) -> Result<(), String> {
+    for (idx, rucksack) in inp.iter().enumerate() {
+        if let Some((_, chr)) = alphabet.find_unknown(rucksack) {
+            return Err(format!(
+                "rucksack {}: unknown item type `{chr}`, expected {}",
+                idx + 1,
+                alphabet.describe()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Byte offset of the first item of the second compartment.
+fn middle(rucksack: &str) -> usize {
+    let half = rucksack.chars().count() / 2;
+    rucksack
+        .char_indices()
+        .nth(half)
+        .map_or(rucksack.len(), |(at, _)| at)
+}
+
+fn group_into_threes(rucksacks: &[String]) -> Vec<Group> {
     let mut group: Vec<Group> = vec![];
 
     for i in 0..(rucksacks.len() / 3) {
@@ -151,50 +221,48 @@ fn group_into_threes(rucksacks: &Vec<String>) -> Vec<Group> {
     group
 }
 
-fn get_common_item_in_group(group: Group) -> Vec<char> {
+fn get_common_item_in_group(group: Group, alphabet: &Alphabet) -> Vec<char> {
     let (str_1, str_2, str_3) = (group.0, group.1, group.2);
-    let mut range = [-1; 52];
+    let mut range = vec![-1; alphabet.len()];
     let mut collected = vec![];
 
-    for chr in str_1.chars().into_iter() {
-        range[get_index_from_char(&chr)] = 0;
+    for idx in str_1.chars().filter_map(|chr| alphabet.index(chr)) {
+        range[idx] = 0;
     }
 
-    for chr in str_2.chars().into_iter() {
-        let idx = get_index_from_char(&chr);
+    for idx in str_2.chars().filter_map(|chr| alphabet.index(chr)) {
         if range[idx] == 0 {
             range[idx] = 1;
         }
     }
 
-    for chr in str_3.chars().into_iter() {
-        let idx = get_index_from_char(&chr);
+    for chr in str_3.chars() {
+        let Some(idx) = alphabet.index(chr) else {
+            continue;
+        };
         if range[idx] == 1 {
             collected.push(chr);
             range[idx] = 0;
         }
     }
 
-    return collected;
+    collected
 }
 
-/**
- * ASCII Map:
- * A-Z => 65-90
- * a-z => 97-122
-*/
-fn get_common_item((str_1, str_2): (&str, &str)) -> Vec<char> {
-    let mut range = [0; 52]; // [A-Za-z] range
+fn get_common_item((str_1, str_2): (&str, &str), alphabet: &Alphabet) -> Vec<char> {
+    let mut range = vec![0; alphabet.len()];
     let mut collected_commons: Vec<char> = vec![];
 
     // Get count of all chars in the first pocket
-    for chr in str_1.chars().into_iter() {
-        range[get_index_from_char(&chr)] += 2;
+    for idx in str_1.chars().filter_map(|chr| alphabet.index(chr)) {
+        range[idx] += 2;
     }
 
     // Get cont of all chars in the second pocket
-    for chr in str_2.chars().into_iter() {
-        let idx = get_index_from_char(&chr);
+    for chr in str_2.chars() {
+        let Some(idx) = alphabet.index(chr) else {
+            continue;
+        };
         if range[idx] > 0 {
             collected_commons.push(chr);
             range[idx] = 0; // To prevent re-entring the same char
@@ -204,20 +272,94 @@ fn get_common_item((str_1, str_2): (&str, &str)) -> Vec<char> {
     collected_commons
 }
 
-fn convert_to_priority(chr: char) -> i32 {
-    let ascii = chr as u8;
-    if ascii >= 65 && ascii <= 90 {
-        (ascii - 65 + 27) as i32 // A-Z
-    } else {
-        (ascii - 96) as i32 // a-z
+pub struct RucksackReorg;
+
+impl Solution for RucksackReorg {
+    const INPUT_PATH: &'static str = "res/_3_rucksack_reorg.txt";
+
+    type Input = Vec<String>;
+    type Output = i32;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
+    }
+
+    // get_inputs only keeps the puzzle's item types
+    fn part1(inp: &Self::Input) -> Self::Output {
+        sum_misplaced(inp, &Alphabet::puzzle())
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        sum_badges(inp, &Alphabet::puzzle())
     }
 }
 
-fn get_index_from_char(chr: &char) -> usize {
-    let ascii = *chr as u8;
-    if ascii >= 65 && ascii <= 90 {
-        (ascii - 65) as usize // A-Z
-    } else {
-        (ascii - 97 + 26) as usize // a-z
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn part1_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(reorg(&inp), Ok(157));
+    }
+
+    #[test]
+    fn part2_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(stick_sticker(&inp), Ok(70));
+    }
+
+    #[test]
+    fn reject_unknown_items() {
+        assert_eq!(
+            get_inputs("vJrwpWtwJgWrhcsFMMfFFhFp\nvJrw4WtwJgWr\n")
+                .unwrap_err()
+                .to_string(),
+            "day 3 input, line 2, column 5: expected an item type a-z or A-Z, found `4`"
+        );
+
+        let inp = vec![String::from("ab#b")];
+        assert_eq!(
+            reorg(&inp),
+            Err(String::from(
+                "rucksack 1: unknown item type `#`, expected a-z or A-Z"
+            ))
+        );
+        assert!(
+            stick_sticker(&[String::from("aa"), String::from("a1"), String::from("aa")]).is_err()
+        );
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let alphabet = Alphabet::new("a-z=1 A-Z=27 0-9=53 ☃=100 ★=200").unwrap();
+        let raw = "\
+a1☃b2☃
+7★☃★Qz
+q★9☃☃c
+";
+        let inp = get_inputs_with(raw, &alphabet).unwrap();
+
+        // ☃ in the first, ★ in the second and nothing in the third
+        assert_eq!(reorg_with(&inp, &alphabet), Ok(100 + 200));
+        // ★ is in the last two but not the first, only ☃ is in all three
+        assert_eq!(stick_sticker_with(&inp, &alphabet), Ok(100));
+        assert!(get_inputs("a1☃b2☃").is_err());
+    }
+
+    #[test]
+    fn real_input() {
+        assert_eq!(solve_real_input::<RucksackReorg>(), (7553, 2758));
     }
 }
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+/// The puzzle's item types: `a` to `z` are worth 1 to 26, `A` to `Z` 27 to 52.
+pub const PUZZLE_ALPHABET: &str = "a-z=1 A-Z=27";
+
+/// The item types rucksacks may hold, each with its priority, described by
+/// whitespace separated `ITEMS=PRIORITY` fields:
+///
+/// ```text
+/// a-z=1 A-Z=27 0-9=53 ☃=100
+/// ```
+///
+/// `ITEMS` is a single character or an inclusive `FROM-TO` range, whose
+/// characters are worth `PRIORITY`, `PRIORITY + 1` and so on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alphabet {
+    /// Position of each item type in `items`.
+    index: HashMap<char, usize>,
+    items: Vec<(char, i32)>,
+}
+
+impl Alphabet {
+    pub fn new(raw: &str) -> Result<Alphabet, String> {
+        let mut alphabet = Alphabet {
+            index: HashMap::new(),
+            items: vec![],
+        };
+
+        for field in raw.split_whitespace() {
+            let err = |msg: &str| format!("`{field}`: {msg}");
+            let (items, priority) = field
+                .rsplit_once('=')
+                .ok_or_else(|| err("expected `ITEMS=PRIORITY`"))?;
+            let priority: i32 = priority
+                .parse()
+                .map_err(|_| err("the priority must be a number"))?;
+
+            let chars: Vec<char> = items.chars().collect();
+            let range = match chars[..] {
+                [single] => single..=single,
+                [from, '-', to] if from <= to => from..=to,
+                [_, '-', _] => return Err(err("the range must go upwards")),
+                _ => return Err(err("expected a character or a `FROM-TO` range")),
+            };
+
+            for (offset, chr) in range.enumerate() {
+                if alphabet.index.insert(chr, alphabet.items.len()).is_some() {
+                    return Err(err(&format!("item type `{chr}` is listed twice")));
+                }
+                let priority = i32::try_from(offset)
+                    .ok()
+                    .and_then(|offset| priority.checked_add(offset))
+                    .ok_or_else(|| err(&format!("the priority of `{chr}` is too large")))?;
+                alphabet.items.push((chr, priority));
+            }
+        }
+
+        if alphabet.items.is_empty() {
+            return Err(String::from("the alphabet has no item type"));
+        }
+
+        Ok(alphabet)
+    }
+
+    pub fn puzzle() -> Alphabet {
+        Alphabet::new(PUZZLE_ALPHABET).unwrap()
+    }
+
+    /// Number of item types.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Position of `chr` among the item types, from 0 to `len() - 1`.
+    pub fn index(&self, chr: char) -> Option<usize> {
+        self.index.get(&chr).copied()
+    }
+
+    pub fn priority(&self, chr: char) -> Option<i32> {
+        Some(self.items[self.index(chr)?].1)
+    }
+
+    /// The first character of `rucksack` that is not an item type.
+    pub fn find_unknown(&self, rucksack: &str) -> Option<(usize, char)> {
+        rucksack
+            .char_indices()
+            .find(|(_, chr)| self.index(*chr).is_none())
+    }
+
+    /// `a-z`, `a-z or 0-9` and so on, as the alphabet was described.
+    pub fn describe(&self) -> String {
+        let mut ranges: Vec<String> = vec![];
+        let mut start = 0;
+
+        for idx in 1..=self.items.len() {
+            let (prev, _) = self.items[idx - 1];
+            let continues = self
+                .items
+                .get(idx)
+                .is_some_and(|(chr, _)| u32::from(*chr) == u32::from(prev) + 1);
+            if !continues {
+                let (first, _) = self.items[start];
+                ranges.push(match idx - start {
+                    1 => format!("{first}"),
+                    _ => format!("{first}-{prev}"),
+                });
+                start = idx;
+            }
+        }
+
+        match ranges.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+            None => String::from("nothing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_alphabet() {
+        let alphabet = Alphabet::puzzle();
+
+        assert_eq!(alphabet.len(), 52);
+        assert_eq!(alphabet.priority('p'), Some(16));
+        assert_eq!(alphabet.priority('L'), Some(38));
+        assert_eq!(alphabet.priority('1'), None);
+        assert_eq!(alphabet.find_unknown("vJr-wp"), Some((3, '-')));
+        assert_eq!(alphabet.describe(), "a-z or A-Z");
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let alphabet = Alphabet::new("a-c=1 0-9=10 ☃=100 -=7 ==8").unwrap();
+
+        assert_eq!(alphabet.len(), 16);
+        assert_eq!(alphabet.priority('c'), Some(3));
+        assert_eq!(alphabet.priority('9'), Some(19));
+        assert_eq!(alphabet.priority('☃'), Some(100));
+        assert_eq!(alphabet.priority('-'), Some(7));
+        assert_eq!(alphabet.priority('='), Some(8));
+        assert_eq!(alphabet.find_unknown("ab☃d"), Some((5, 'd')));
+        assert_eq!(alphabet.describe(), "a-c, 0-9, ☃, - or =");
+    }
+
+    #[test]
+    fn reject_invalid_alphabets() {
+        let err = |raw| Alphabet::new(raw).unwrap_err();
+
+        assert_eq!(err("a-z=1 x=30"), "`x=30`: item type `x` is listed twice");
+        assert_eq!(err("z-a=1"), "`z-a=1`: the range must go upwards");
+        assert_eq!(
+            err("ab=1"),
+            "`ab=1`: expected a character or a `FROM-TO` range"
+        );
+        assert_eq!(err("a=high"), "`a=high`: the priority must be a number");
+        assert_eq!(
+            err("a-c=2147483646"),
+            "`a-c=2147483646`: the priority of `c` is too large"
+        );
+        assert_eq!(err("a"), "`a`: expected `ITEMS=PRIORITY`");
+        assert_eq!(err(" "), "the alphabet has no item type");
+    }
+}
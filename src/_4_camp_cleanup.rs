@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 4: Camp Cleanup ---
  * Space needs to be cleared before the last supplies can be unloaded from the
  * ships, and so several Elves have been assigned the job of cleaning up
@@ -60,64 +60,78 @@
  * example, there are 2 such pairs.
  *
  * In how many assignment pairs does one range fully contain the other?
- * 
+ *
  * --- Part Two ---
- * It seems like there is still quite a bit of duplicate work planned. Instead, 
+ * It seems like there is still quite a bit of duplicate work planned. Instead,
  * the Elves would like to know the number of pairs that overlap at all.
- * 
- * In the above example, the first two pairs (2-4,6-8 and 2-3,4-5) don't overlap, 
- * while the remaining four pairs (5-7,7-9, 2-8,3-7, 6-6,4-6, and 2-6,4-8) do 
+ *
+ * In the above example, the first two pairs (2-4,6-8 and 2-3,4-5) don't overlap,
+ * while the remaining four pairs (5-7,7-9, 2-8,3-7, 6-6,4-6, and 2-6,4-8) do
  * overlap:
- * 
+ *
  * 5-7,7-9 overlaps in a single section, 7.
  * 2-8,3-7 overlaps all of the sections 3 through 7.
  * 6-6,4-6 overlaps in a single section, 6.
  * 2-6,4-8 overlaps in sections 4, 5, and 6.
- * 
+ *
  * So, in this example, the number of overlapping assignment pairs is 4.
- * 
+ *
  * In how many assignment pairs do the ranges overlap?
 */
-use std::fs;
+use crate::error::{parse_token, ParseError};
+use crate::input::{lines, Line};
+use crate::solution::Solution;
 
 #[derive(Debug, Clone)]
-pub struct Pair<T>(T, T);
+pub struct Pair<T>(pub T, pub T);
 
 pub type Input = Vec<Pair<Pair<i32>>>;
 
-pub fn get_inputs() -> Input {
-    fs::read_to_string("res/_4_camp_cleanup.txt")
-        .unwrap()
-        .split("\r\n")
-        .map(|each| each.split(",").take(2))
-        .map(|each_pair| {
-            let pairs: Vec<Pair<i32>> = each_pair
-                .map(|section| {
-                    let section_set: Vec<&str> = section.split("-").take(2).collect();
-                    let section_start: i32 = section_set.get(0).unwrap().parse().unwrap();
-                    let section_end: i32 = section_set.get(1).unwrap().parse().unwrap();
-                    Pair(section_start, section_end)
-                })
-                .take(2)
-                .collect();
-            Pair(
-                pairs.get(0).unwrap().to_owned(),
-                pairs.get(1).unwrap().to_owned(),
-            )
+const DAY: u8 = 4;
+
+pub fn get_inputs(raw: &str) -> Result<Input, ParseError> {
+    lines(raw)
+        .into_iter()
+        .map(|line| {
+            let (first, second) = line
+                .1
+                .split_once(',')
+                .ok_or_else(|| ParseError::missing(DAY, line, "`,` between the two assignments"))?;
+            Ok(Pair(
+                parse_section(line, first)?,
+                parse_section(line, second)?,
+            ))
         })
         .collect()
 }
 
+fn parse_section(line: Line, section: &str) -> Result<Pair<i32>, ParseError> {
+    let (start, end) = section
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(DAY, line, section, "a section range like `2-4`"))?;
+
+    let section_start: i32 = parse_token(DAY, line, start, "a section ID")?;
+    let section_end: i32 = parse_token(DAY, line, end, "a section ID")?;
+    if section_start > section_end {
+        return Err(ParseError::at(
+            DAY,
+            line,
+            section,
+            "a range that does not end before it starts",
+        ));
+    }
+
+    Ok(Pair(section_start, section_end))
+}
+
 // part 1 sol
-pub fn get_highest_recommendation(inp: Input) -> usize {
-    let high_priority_secs: Input = inp.into_iter().filter(is_contains).collect();
-    high_priority_secs.len()
+pub fn get_highest_recommendation(inp: &[Pair<Pair<i32>>]) -> usize {
+    inp.iter().filter(|sections| is_contains(sections)).count()
 }
 
 // part 2 sol
-pub fn get_overlaps(inp: Input) -> usize {
-    let high_priority_secs: Input = inp.into_iter().filter(is_overlap).collect();
-    high_priority_secs.len()
+pub fn get_overlaps(inp: &[Pair<Pair<i32>>]) -> usize {
+    inp.iter().filter(|sections| is_overlap(sections)).count()
 }
 
 fn is_contains(sections: &Pair<Pair<i32>>) -> bool {
@@ -127,13 +141,88 @@ fn is_contains(sections: &Pair<Pair<i32>>) -> bool {
 }
 
 fn is_overlap(sections: &Pair<Pair<i32>>) -> bool {
-    let outer_overlap = 
-    (sections.0.0 >= sections.1.0 && sections.0.0 <= sections.1.1) ||
-    (sections.0.1 >= sections.1.0 && sections.0.1 <= sections.1.1);
+    let outer_overlap = (sections.0 .0 >= sections.1 .0 && sections.0 .0 <= sections.1 .1)
+        || (sections.0 .1 >= sections.1 .0 && sections.0 .1 <= sections.1 .1);
+
+    let inner_overlap = (sections.1 .0 >= sections.0 .0 && sections.1 .0 <= sections.0 .1)
+        || (sections.1 .1 >= sections.0 .0 && sections.1 .1 <= sections.0 .1);
+
+    outer_overlap || inner_overlap
+}
+
+pub struct CampCleanup;
+
+impl Solution for CampCleanup {
+    const INPUT_PATH: &'static str = "res/_4_camp_cleanup.txt";
+
+    type Input = Input;
+    type Output = usize;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        get_highest_recommendation(inp)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        get_overlaps(inp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn part1_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(get_highest_recommendation(&inp), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(get_overlaps(&inp), 4);
+    }
+
+    #[test]
+    fn report_parse_errors() {
+        let err = |raw| get_inputs(raw).unwrap_err().to_string();
 
-    let inner_overlap =
-    (sections.1.0 >= sections.0.0 && sections.1.0 <= sections.0.1) ||
-    (sections.1.1 >= sections.0.0 && sections.1.1 <= sections.0.1);
+        assert_eq!(
+            err("2-4,6-8\n2-3 4-5\n"),
+            "day 4 input, line 2, column 8: expected `,` between the two assignments, \
+            found end of line"
+        );
+        assert_eq!(
+            err("2-4,6-x\n"),
+            "day 4 input, line 1, column 7: expected a section ID, found `x`"
+        );
+        assert_eq!(
+            err("2-4,6-8\n2-4,68\n"),
+            "day 4 input, line 2, column 5: expected a section range like `2-4`, found `68`"
+        );
+        assert_eq!(
+            err("5-3,1-2\n"),
+            "day 4 input, line 1, column 1: expected a range that does not end before it \
+            starts, found `5-3`"
+        );
+    }
 
-    return outer_overlap || inner_overlap
+    #[test]
+    fn real_input() {
+        assert_eq!(solve_real_input::<CampCleanup>(), (509, 870));
+    }
 }
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 5: Supply Stacks ---
  * The expedition can depart as soon as the final supplies have been unloaded
  * from the ships. Supplies are stored in stacks of marked crates, but because
@@ -17,8 +17,8 @@
  * They do, however, have a drawing of the starting stacks of crates and the
  * rearrangement procedure (your puzzle input). For example:
  *
- *     [D]    
- * [N] [C]    
+ *     [D]
+ * [N] [C]
  * [Z] [M] [P]
  * 1   2   3
  * move 1 from 2 to 1
@@ -35,8 +35,8 @@
  * quantity of crates is moved from one stack to a different stack. In the first
  * step of the above rearrangement procedure, one crate is moved from stack 2 to
  * stack 1, resulting in this configuration:
- * [D]        
- * [N] [C]    
+ * [D]
+ * [N] [C]
  * [Z] [M] [P]
  *  1   2   3
  * In the second step, three crates are moved from stack 1 to stack 3. Crates
@@ -84,14 +84,14 @@
  * Again considering the example above, the crates begin in the same
  * configuration:
  *
- *     [D]    
- * [N] [C]    
+ *     [D]
+ * [N] [C]
  * [Z] [M] [P]
  *  1   2   3
  * Moving a single crate from stack 2 to stack 1 behaves the same as before:
  *
- * [D]        
- * [N] [C]    
+ * [D]
+ * [N] [C]
  * [Z] [M] [P]
  *  1   2   3
  * However, the action of moving three crates from stack 1 to stack 3 means that
@@ -127,7 +127,11 @@
  * After the rearrangement procedure completes, what crate ends up on top of
  * each stack?
 */
-use std::fs;
+use crate::error::{parse_token, ParseError};
+use crate::input::{paragraphs, Line};
+use crate::solution::Solution;
+
+const DAY: u8 = 5;
 
 #[derive(Debug, Clone)]
 pub struct Command(usize, usize, usize);
@@ -135,24 +139,63 @@ pub struct Command(usize, usize, usize);
 pub type CommandList = Vec<Command>;
 
 impl Command {
-    fn new(cmd_str: &str) -> Command {
-        let cmd_list: Vec<&str> = cmd_str.split_whitespace().collect();
-        let number_of_items: usize = cmd_list.get(1).unwrap().parse().unwrap();
-        let from_column: usize = cmd_list.get(3).unwrap().parse().unwrap();
-        let to_column: usize = cmd_list.get(5).unwrap().parse().unwrap();
+    fn new(line: Line, number_of_stacks: usize) -> Result<Command, ParseError> {
+        let mut cmd_list = line.1.split_whitespace();
+        let is_stack = |stack: usize| (1..=number_of_stacks).contains(&stack);
+        let stack_expected = format!("a stack between 1 and {number_of_stacks}");
+
+        let number_of_items = Command::parse_arg(
+            line,
+            &mut cmd_list,
+            "move",
+            "the number of crates to move",
+            |_| true,
+        )?;
+        let from_column =
+            Command::parse_arg(line, &mut cmd_list, "from", &stack_expected, is_stack)?;
+        let to_column = Command::parse_arg(line, &mut cmd_list, "to", &stack_expected, is_stack)?;
+
+        if let Some(extra) = cmd_list.next() {
+            return Err(ParseError::at(DAY, line, extra, "end of line"));
+        }
+
+        Ok(Command(number_of_items, from_column, to_column))
+    }
+
+    fn parse_arg<'a>(
+        line: Line<'a>,
+        cmd_list: &mut impl Iterator<Item = &'a str>,
+        keyword: &str,
+        expected: &str,
+        is_valid: impl Fn(usize) -> bool,
+    ) -> Result<usize, ParseError> {
+        let keyword_expected = format!("`{keyword}`");
+        match cmd_list.next() {
+            Some(token) if token == keyword => (),
+            Some(token) => return Err(ParseError::at(DAY, line, token, &keyword_expected)),
+            None => return Err(ParseError::missing(DAY, line, &keyword_expected)),
+        }
+
+        let token = cmd_list
+            .next()
+            .ok_or_else(|| ParseError::missing(DAY, line, expected))?;
+        let value = parse_token(DAY, line, token, expected)?;
+        if !is_valid(value) {
+            return Err(ParseError::at(DAY, line, token, expected));
+        }
 
-        Command(number_of_items, from_column, to_column)
+        Ok(value)
     }
 
-    fn get_number_of_items(&self) -> usize {
+    pub fn get_number_of_items(&self) -> usize {
         self.0
     }
 
-    fn get_from_column(&self) -> usize {
+    pub fn get_from_column(&self) -> usize {
         self.1
     }
 
-    fn get_to_column(&self) -> usize {
+    pub fn get_to_column(&self) -> usize {
         self.2
     }
 }
@@ -160,94 +203,112 @@ impl Command {
 pub type Cell = char;
 pub type Stack = Vec<Cell>;
 
+#[derive(Debug, Clone)]
 pub struct Cargo {
     stacks: Vec<Stack>,
 }
 
 impl Cargo {
-    fn new(stacks_str: &str) -> Cargo {
-        let mut cargo_rows: Vec<&str> = stacks_str.split("\r\n").collect();
+    fn new(stacks_rows: &[Line]) -> Result<Cargo, ParseError> {
+        let mut cargo_rows: Vec<Line> = stacks_rows.to_vec();
         cargo_rows.reverse();
-        let number_of_columns = Cargo::get_number_of_stacks(&cargo_rows);
+        let number_of_columns = Cargo::get_number_of_stacks(&cargo_rows)?;
 
-        let cargo_rows = cargo_rows.into_iter().skip(1).collect::<Vec<&str>>();
-        let cargo_rows = Cargo::create_rows_in_cells(cargo_rows, number_of_columns);
+        let cargo_rows = cargo_rows.into_iter().skip(1).collect::<Vec<Line>>();
+        let cargo_rows = Cargo::create_rows_in_cells(cargo_rows, number_of_columns)?;
         let cargo_stack = Cargo::transform_to_neg_90_deg(&cargo_rows, number_of_columns);
 
-        Cargo {
+        Ok(Cargo {
             stacks: cargo_stack,
-        }
+        })
     }
 
-    fn exec_cmd(&mut self, cmd: &Command) {
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    pub fn exec_cmd(&mut self, cmd: &Command) {
         let from_stack = cmd.get_from_column() - 1;
         let to_stack = cmd.get_to_column() - 1;
         for _ in 0..cmd.get_number_of_items() {
-            let item = self.stacks.get_mut(from_stack).unwrap().pop().unwrap();
-            self.stacks.get_mut(to_stack).unwrap().push(item);
+            let Some(item) = self.stacks[from_stack].pop() else {
+                break;
+            };
+            self.stacks[to_stack].push(item);
         }
     }
 
-    fn exec_cmd_v2(&mut self, cmd: &Command) {
-        let mut temp_stack: Stack = vec![];
-
-        let from_stack = cmd.get_from_column() - 1;
-        let to_stack = cmd.get_to_column() - 1;
+    pub fn exec_cmd_v2(&mut self, cmd: &Command) {
+        let from_stack = &mut self.stacks[cmd.get_from_column() - 1];
+        let at = from_stack.len().saturating_sub(cmd.get_number_of_items());
+        let temp_stack = from_stack.split_off(at);
 
-        for _ in 0..cmd.get_number_of_items() {
-            let item = self.stacks.get_mut(from_stack).unwrap().pop().unwrap();
-            temp_stack.push(item);
-        }
+        self.stacks[cmd.get_to_column() - 1].extend(temp_stack);
+    }
 
-        for _ in 0..cmd.get_number_of_items() {
-            let item = temp_stack.pop().unwrap().to_owned();
-            self.stacks.get_mut(to_stack).unwrap().push(item);
-        }
+    /// The crate on top of each stack, leaving out the empty ones.
+    pub fn top_to_string(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .collect()
     }
 
-    fn top_to_string(&self) -> String {
-        let mut base_chars = vec![];
+    fn get_number_of_stacks(cargo_rows: &[Line]) -> Result<usize, ParseError> {
+        // The paragraph is never empty, so the stack labels row is always there
+        let labels_row = cargo_rows[0];
 
-        for stack in self.stacks.iter() {
-            base_chars.push(stack.last().unwrap().to_owned())
+        let mut number_of_stacks = 0;
+        for label in labels_row.1.split_whitespace() {
+            number_of_stacks += 1;
+            let expected = format!("stack label `{number_of_stacks}`");
+            if parse_token::<usize>(DAY, labels_row, label, &expected)? != number_of_stacks {
+                return Err(ParseError::at(DAY, labels_row, label, &expected));
+            }
         }
 
-        base_chars.iter().collect()
+        Ok(number_of_stacks)
     }
 
-    fn get_number_of_stacks(cargo_rows: &Vec<&str>) -> usize {
-        cargo_rows
-            .get(0)
-            .unwrap()
-            .split_whitespace()
-            .filter(|each| !each.is_empty())
-            .collect::<Vec<&str>>()
-            .len()
-    }
-
-    fn create_rows_in_cells(cargo_rows: Vec<&str>, number_of_columns: usize) -> Vec<Vec<Cell>> {
+    fn create_rows_in_cells(
+        cargo_rows: Vec<Line>,
+        number_of_columns: usize,
+    ) -> Result<Vec<Vec<Cell>>, ParseError> {
         let mut rows = vec![];
 
-        for row in cargo_rows.into_iter() {
+        for line in cargo_rows.into_iter() {
             let mut row_cells = vec![];
 
-            let row = row.chars().collect::<Vec<char>>();
+            let (_, row) = line;
             for i in 0..number_of_columns {
-                let start_idx = i * 4;
-                let content = row.get(start_idx + 1).unwrap().to_owned();
+                let start_idx = (i * 4).min(row.len());
+                let end_idx = (start_idx + 3).min(row.len());
+
+                // Editors tend to strip trailing blanks, so a short row only
+                // means the remaining slots are empty
+                let cell = row.get(start_idx..end_idx).unwrap_or(row);
+                let content = match cell.as_bytes() {
+                    [b'[', content, b']'] if content.is_ascii_graphic() => *content as char,
+                    _ if cell.trim().is_empty() => ' ',
+                    _ => {
+                        return Err(ParseError::at(
+                            DAY,
+                            line,
+                            cell,
+                            "a crate like `[A]` or an empty slot",
+                        ))
+                    }
+                };
                 row_cells.push(content);
             }
 
             rows.push(row_cells);
         }
 
-        rows
+        Ok(rows)
     }
 
-    fn transform_to_neg_90_deg(
-        cargo_rows: &Vec<Vec<Cell>>,
-        number_of_columns: usize,
-    ) -> Vec<Stack> {
+    fn transform_to_neg_90_deg(cargo_rows: &[Vec<Cell>], number_of_columns: usize) -> Vec<Stack> {
         let mut stack_list = vec![vec![]; number_of_columns];
 
         for row in cargo_rows.iter() {
@@ -259,43 +320,185 @@ impl Cargo {
             }
         }
 
-        return stack_list;
+        stack_list
     }
 }
 
 pub type Input = (CommandList, Cargo);
 
-pub fn get_inputs() -> Input {
-    let file = fs::read_to_string("res/_5_supply_stacks.txt").unwrap();
-
-    let inputs: Vec<&str> = file.split("\r\n\r\n").collect();
-    let stacks_str = inputs.get(0).unwrap().to_owned();
-    let commands_str = inputs.get(1).unwrap().to_owned();
+pub fn get_inputs(raw: &str) -> Result<Input, ParseError> {
+    let inputs = paragraphs(raw);
+    let stacks_rows = inputs
+        .first()
+        .ok_or_else(|| ParseError::eof(DAY, 1, "a drawing of the crate stacks"))?;
+    let commands_rows = inputs.get(1).ok_or_else(|| {
+        let (last_line, _) = stacks_rows[stacks_rows.len() - 1];
+        ParseError::eof(DAY, last_line + 2, "the rearrangement procedure")
+    })?;
+
+    let cargo = Cargo::new(stacks_rows)?;
+    let mut heights: Vec<usize> = cargo.stacks.iter().map(|stack| stack.len()).collect();
+    let mut cmds = CommandList::new();
+
+    for line in commands_rows.iter() {
+        let cmd = Command::new(*line, cargo.stacks.len())?;
+        let (from, to) = (cmd.get_from_column() - 1, cmd.get_to_column() - 1);
+
+        // Both parts move as many crates, so one count serves them both
+        if cmd.get_number_of_items() > heights[from] {
+            let count = line.1.split_whitespace().nth(1).unwrap_or(line.1);
+            let expected = format!(
+                "at most {} crates, as many as stack {} holds",
+                heights[from],
+                from + 1
+            );
+            return Err(ParseError::at(DAY, *line, count, &expected));
+        }
+        heights[from] -= cmd.get_number_of_items();
+        heights[to] += cmd.get_number_of_items();
 
-    let cmds: CommandList = commands_str.split("\r\n").map(Command::new).collect();
-    let cargo = Cargo::new(stacks_str);
+        cmds.push(cmd);
+    }
 
-    (cmds, cargo)
+    Ok((cmds, cargo))
 }
 
 // part 1 sol
-pub fn rearrange(inp: &mut Input) -> String {
-    let (cmds, cargo) = inp;
-
+pub fn rearrange(cmds: &[Command], mut cargo: Cargo) -> String {
     for cmd in cmds.iter() {
         cargo.exec_cmd(cmd);
     }
 
-    String::from(cargo.top_to_string())
+    cargo.top_to_string()
 }
 
 // part 2 sol
-pub fn rearrange_v2(inp: &mut Input) -> String {
-    let (cmds, cargo) = inp;
-
+pub fn rearrange_v2(cmds: &[Command], mut cargo: Cargo) -> String {
     for cmd in cmds.iter() {
         cargo.exec_cmd_v2(cmd);
     }
 
-    String::from(cargo.top_to_string())
+    cargo.top_to_string()
+}
+
+pub struct SupplyStacks;
+
+impl Solution for SupplyStacks {
+    const INPUT_PATH: &'static str = "res/_5_supply_stacks.txt";
+
+    type Input = Input;
+    type Output = String;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        let (cmds, cargo) = inp;
+        rearrange(cmds, cargo.clone())
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        let (cmds, cargo) = inp;
+        rearrange_v2(cmds, cargo.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn part1_example() {
+        let (cmds, cargo) = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(rearrange(&cmds, cargo), "CMZ");
+    }
+
+    #[test]
+    fn part2_example() {
+        let (cmds, cargo) = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(rearrange_v2(&cmds, cargo), "MCD");
+    }
+
+    #[test]
+    fn report_command_errors() {
+        let err = |text| Command::new((7, text), 3).unwrap_err().to_string();
+
+        assert_eq!(
+            err("move 1 form 2 to 1"),
+            "day 5 input, line 7, column 8: expected `from`, found `form`"
+        );
+        assert_eq!(
+            err("move x from 2 to 1"),
+            "day 5 input, line 7, column 6: expected the number of crates to move, found `x`"
+        );
+        assert_eq!(
+            err("move 1 from 4 to 1"),
+            "day 5 input, line 7, column 13: expected a stack between 1 and 3, found `4`"
+        );
+        assert_eq!(
+            err("move 1 from 2"),
+            "day 5 input, line 7, column 14: expected `to`, found end of line"
+        );
+        assert_eq!(
+            err("move 1 from 2 to 1 now"),
+            "day 5 input, line 7, column 20: expected end of line, found `now`"
+        );
+    }
+
+    #[test]
+    fn report_drawing_errors() {
+        let err = Cargo::create_rows_in_cells(vec![(1, "[A]"), (2, "[A] {B} [C]")], 3)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "day 5 input, line 2, column 5: expected a crate like `[A]` or an empty slot, \
+            found `{B}`"
+        );
+
+        let err = get_inputs("    [D]\n[N] [C]\n 1   3\n\nmove 1 from 2 to 1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+        assert_eq!(err.expected, "stack label `2`");
+
+        let err = get_inputs("    [D]\n[N] [C]\n 1   2\n").unwrap_err();
+        assert_eq!((err.line, err.found.as_str()), (5, "end of input"));
+    }
+
+    #[test]
+    fn report_moves_past_the_stack() {
+        let raw = format!("{EXAMPLE}move 5 from 2 to 1\n");
+        assert_eq!(
+            get_inputs(&raw).unwrap_err().to_string(),
+            "day 5 input, line 10, column 6: expected at most 1 crates, as many as stack 2 holds, \
+            found `5`"
+        );
+    }
+
+    #[test]
+    fn skip_emptied_stacks() {
+        let raw = EXAMPLE.replace("move 1 from 1 to 2\n", "move 1 from 3 to 1\n");
+        let (cmds, cargo) = get_inputs(&raw).unwrap();
+        // Stack 2 ends up empty
+        assert_eq!(rearrange(&cmds, cargo.clone()), "ZN");
+        assert_eq!(rearrange_v2(&cmds, cargo), "DN");
+    }
+
+    #[test]
+    fn real_input() {
+        let (ans_1, ans_2) = solve_real_input::<SupplyStacks>();
+        assert_eq!((ans_1.as_str(), ans_2.as_str()), ("TWSGQHNHL", "JNRSCDWPP"));
+    }
 }
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 6: Tuning Trouble ---
  * The preparations are finally complete; you and the Elves leave camp on foot
  * and begin to make your way toward the star fruit grove.
@@ -71,10 +71,12 @@
  * How many characters need to be processed before the first start-of-message
  * marker is detected?
 */
-use std::fs;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
 
-pub fn get_inputs() -> String {
-    fs::read_to_string("res/_6_tuning_trouble.txt").unwrap()
+pub fn get_inputs(raw: &str) -> Result<String, ParseError> {
+    Ok(lines(raw).into_iter().map(|(_, text)| text).collect())
 }
 
 #[derive(Debug, Clone)]
@@ -98,7 +100,7 @@ impl Buff {
     }
 
     fn is_buff_full(&self) -> bool {
-        return self.empty_idx == self.size;
+        self.empty_idx == self.size
     }
 
     fn is_in_buff(&self, chr: char) -> (bool, usize) {
@@ -121,7 +123,7 @@ impl Buff {
 }
 
 // part 1 & 2 sol
-pub fn find_index(inp: String, size: usize) -> usize {
+pub fn find_index(inp: &str, size: usize) -> usize {
     let mut i: usize = 0;
     let chars = inp.chars().collect::<Vec<char>>();
     let mut bff = Buff::new(size);
@@ -144,3 +146,60 @@ pub fn find_index(inp: String, size: usize) -> usize {
 
     0
 }
+
+pub struct TuningTrouble;
+
+impl Solution for TuningTrouble {
+    const INPUT_PATH: &'static str = "res/_6_tuning_trouble.txt";
+
+    type Input = String;
+    type Output = usize;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        find_index(inp, 4)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        find_index(inp, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    // (datastream, start-of-packet, start-of-message)
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn part1_examples() {
+        for (datastream, packet_idx, _) in EXAMPLES {
+            let inp = get_inputs(datastream).unwrap();
+            assert_eq!(find_index(&inp, 4), packet_idx, "{datastream}");
+        }
+    }
+
+    #[test]
+    fn part2_examples() {
+        for (datastream, _, message_idx) in EXAMPLES {
+            let inp = get_inputs(datastream).unwrap();
+            assert_eq!(find_index(&inp, 14), message_idx, "{datastream}");
+        }
+    }
+
+    #[test]
+    fn real_input() {
+        assert_eq!(solve_real_input::<TuningTrouble>(), (1723, 3708));
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 7: No Space Left On Device ---
  * You can hear birds chirping and raindrops hitting leaves as the expedition
  * proceeds. Occasionally, you can even hear much louder sounds in the distance;
@@ -105,39 +105,43 @@
  *
  * Find all of the directories with a total size of at most 100000. What is the
  * sum of the total sizes of those directories?
- * 
+ *
  * --- Part Two ---
  * Now, you're ready to choose a directory to delete.
- * 
- * The total disk space available to the filesystem is 70000000. To run the 
- * update, you need unused space of at least 30000000. You need to find a 
+ *
+ * The total disk space available to the filesystem is 70000000. To run the
+ * update, you need unused space of at least 30000000. You need to find a
  * directory you can delete that will free up enough space to run the update.
- * 
- * In the example above, the total size of the outermost directory (and thus the 
- * total amount of used space) is 48381165; this means that the size of the 
- * unused space must currently be 21618835, which isn't quite the 30000000 
- * required by the update. Therefore, the update still requires a directory with 
+ *
+ * In the example above, the total size of the outermost directory (and thus the
+ * total amount of used space) is 48381165; this means that the size of the
+ * unused space must currently be 21618835, which isn't quite the 30000000
+ * required by the update. Therefore, the update still requires a directory with
  * total size of at least 8381165 to be deleted before it can run.
- * 
+ *
  * To achieve this, you have the following options:
- * 
+ *
  * Delete directory e, which would increase unused space by 584.
  * Delete directory a, which would increase unused space by 94853.
  * Delete directory d, which would increase unused space by 24933642.
  * Delete directory /, which would increase unused space by 48381165.
- * Directories e and a are both too small; deleting them would not free up 
- * enough space. However, directories d and / are both big enough! Between 
+ * Directories e and a are both too small; deleting them would not free up
+ * enough space. However, directories d and / are both big enough! Between
  * these, choose the smallest: d, increasing unused space by 24933642.
- * 
- * Find the smallest directory that, if deleted, would free up enough space on 
+ *
+ * Find the smallest directory that, if deleted, would free up enough space on
  * the filesystem to run the update. What is the total size of that directory?
 */
 use regex::Regex;
-use std::{time, fs};
 
-const ROOT_DIR_NAME: &'static str = "/";
-const PREV_DIR_NAME: &'static str = "..";
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+const ROOT_DIR_NAME: &str = "/";
+const PREV_DIR_NAME: &str = "..";
 const MAX_SSD_SPACE: i32 = 70000000;
+const DAY: u8 = 7;
 
 #[derive(Debug)]
 pub enum Command {
@@ -195,7 +199,7 @@ impl Command {
     fn create_touch_cmd(cmd_str: &str) -> Option<Command> {
         let mkdir_cmd_regex = Regex::new(r"^([0-9]+) (.+)$").unwrap();
         if let Some(captures) = mkdir_cmd_regex.captures(cmd_str) {
-            let size = String::from(&captures[1]).parse::<i32>().unwrap();
+            let size = String::from(&captures[1]).parse::<i32>().ok()?;
             let file_name = String::from(&captures[2]);
             return Some(Command::Touch(size, file_name));
         }
@@ -203,7 +207,7 @@ impl Command {
     }
 }
 
-type FilePtr = usize;
+pub type FilePtr = usize;
 
 #[derive(Debug)]
 pub struct File {
@@ -215,21 +219,21 @@ pub struct File {
 }
 
 impl File {
-    fn new(name: &str, parent_file: Option<FilePtr>, location: FilePtr, size: i32) -> Box<File> {
-        Box::new(File {
+    fn new(name: &str, parent_file: Option<FilePtr>, location: FilePtr, size: i32) -> File {
+        File {
             name: String::from(name),
             parent_file,
             children_files: None,
             size,
             location,
-        })
+        }
     }
 
-    fn is_dir(&self) -> bool {
-        !self.children_files.is_none()
+    pub fn is_dir(&self) -> bool {
+        self.children_files.is_some()
     }
 
-    fn get_name(&self) -> &String {
+    pub fn get_name(&self) -> &String {
         &self.name
     }
 
@@ -237,22 +241,19 @@ impl File {
         self.size += size;
     }
 
-    fn get_size(&self) -> i32 {
-        self.size.clone()
+    pub fn get_size(&self) -> i32 {
+        self.size
     }
 
-    fn get_location(&self) -> FilePtr {
-        self.location.clone()
+    pub fn get_location(&self) -> FilePtr {
+        self.location
     }
 
-    fn get_parent(&self) -> Option<FilePtr> {
-        self.parent_file.clone()
+    pub fn get_parent(&self) -> Option<FilePtr> {
+        self.parent_file
     }
 
-    fn get_children(&self) -> Option<&Vec<FilePtr>> {
-        if self.children_files.is_none() {
-            return None;
-        }
+    pub fn get_children(&self) -> Option<&Vec<FilePtr>> {
         self.children_files.as_ref()
     }
 
@@ -264,10 +265,11 @@ impl File {
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug)]
 pub struct SSD {
     curr_dir: FilePtr,
-    files: Vec<Box<File>>,
+    files: Vec<File>,
 }
 
 impl SSD {
@@ -282,7 +284,7 @@ impl SSD {
         let mut next_location = location;
         loop {
             self.files.get_mut(next_location).unwrap().update_size(size);
-            if let Some(parent )= self.files.get(next_location).unwrap().get_parent() {
+            if let Some(parent) = self.files.get(next_location).unwrap().get_parent() {
                 next_location = parent;
                 continue;
             }
@@ -290,40 +292,44 @@ impl SSD {
         }
     }
 
-    fn get_used_space(&self) -> i32 {
-        self.files.get(0).unwrap().get_size()
+    pub fn get_used_space(&self) -> i32 {
+        self.files.first().unwrap().get_size()
     }
 
-    fn get_free_space(&self) -> i32 {
+    pub fn get_free_space(&self) -> i32 {
         MAX_SSD_SPACE - self.get_used_space()
     }
 
-    fn get_all_files(&self) -> &Vec<Box<File>> {
+    pub fn get_all_files(&self) -> &[File] {
         &self.files
     }
 
-    fn exec_cd(&mut self, dir_name: &String) {
+    /// Fails with what `dir_name` should have been when there is no such
+    /// directory to move to.
+    fn exec_cd(&mut self, dir_name: &str) -> Result<(), &'static str> {
         if dir_name == ROOT_DIR_NAME {
             self.set_curr_dir_to_root();
-            return;
+            return Ok(());
         }
 
         if dir_name == PREV_DIR_NAME {
-            self.move_to_prev_dir();
-            return;
+            return self
+                .move_to_prev_dir()
+                .ok_or("`/` or a listed directory, `/` has no parent");
         }
 
-        self.move_to_child_dir(&dir_name);
-        return;
+        self.move_to_child_dir(dir_name)
+            .ok_or("`/`, `..` or a directory listed in the current one")
     }
 
+    #[allow(dead_code)]
     fn exec_ls(&self) {
         self.print_disk(self.curr_dir, 1);
     }
 
-    fn exec_mkdir(&mut self, dir_name: &String) {
+    fn exec_mkdir(&mut self, dir_name: &str) {
         let new_location = self.files.len();
-        let file = File::new(&dir_name, Some(self.curr_dir), new_location, 0);
+        let file = File::new(dir_name, Some(self.curr_dir), new_location, 0);
         self.files.push(file);
         self.files
             .get_mut(self.curr_dir)
@@ -333,7 +339,7 @@ impl SSD {
 
     fn exec_touch(&mut self, file_name: &str, size: i32) {
         let new_location = self.files.len();
-        let file = File::new(&file_name, Some(self.curr_dir), new_location, size);
+        let file = File::new(file_name, Some(self.curr_dir), new_location, size);
         self.files.push(file);
         self.update_size(self.curr_dir, size);
         self.files
@@ -346,36 +352,25 @@ impl SSD {
         self.curr_dir = 0;
     }
 
-    fn move_to_prev_dir(&mut self) {
+    fn move_to_prev_dir(&mut self) -> Option<()> {
         let curr_file = self.files.get(self.curr_dir).unwrap();
-        let parent_location = curr_file.get_parent();
-        if parent_location.is_none() {
-            return;
-        }
-        self.curr_dir = parent_location.unwrap();
+        self.curr_dir = curr_file.get_parent()?;
+        Some(())
     }
 
-    fn move_to_child_dir(&mut self, dir_name: &String) {
+    fn move_to_child_dir(&mut self, dir_name: &str) -> Option<()> {
         let curr_file = self.files.get(self.curr_dir).unwrap();
-        let children = curr_file.get_children();
-        if children.is_none() {
-            return;
-        }
-
-        let child = children
-            .unwrap()
+        let child = curr_file
+            .get_children()?
             .iter()
-            .map(|file_location| self.files.get(file_location.clone()))
-            .filter(|file| !file.is_none())
-            .map(|file| file.unwrap())
-            .find(|file| file.get_name() == dir_name);
-        if child.is_none() {
-            return;
-        }
+            .filter_map(|file_location| self.files.get(*file_location))
+            .find(|file| file.get_name() == dir_name)?;
 
-        self.curr_dir = child.unwrap().get_location();
+        self.curr_dir = child.get_location();
+        Some(())
     }
 
+    #[allow(dead_code)]
     fn print_disk(&self, file_ptr: FilePtr, depth: usize) {
         let file = self.files.get(file_ptr).unwrap();
         let name = file.get_name().clone();
@@ -389,47 +384,60 @@ impl SSD {
 
         for child in children.unwrap().iter() {
             print!("{}", "  ".repeat(depth));
-            self.print_disk(child.clone(), depth + 1)
+            self.print_disk(*child, depth + 1)
         }
     }
 }
 
-pub fn get_inputs() -> Vec<Command> {
-    fs::read_to_string("res/_7_no_space.txt")
-        .unwrap()
-        .split("\r\n")
-        .map(|cmd_str| Command::new(cmd_str))
-        .filter(|cmd| !cmd.is_none())
-        .map(|cmd| cmd.unwrap())
-        .collect::<Vec<Command>>()
+/// Reads the terminal output, each command with its line number.
+pub fn get_inputs(raw: &str) -> Result<Vec<(usize, Command)>, ParseError> {
+    lines(raw)
+        .into_iter()
+        .map(|line| {
+            let cmd = Command::new(line.1).ok_or_else(|| {
+                ParseError::at(
+                    DAY,
+                    line,
+                    line.1,
+                    "`$ cd <dir>`, `$ ls`, `dir <name>` or `<size> <name>`",
+                )
+            })?;
+            Ok((line.0, cmd))
+        })
+        .collect::<Result<Vec<(usize, Command)>, ParseError>>()
 }
 
-pub fn setup_ssd(inp: &Vec<Command>) -> SSD {
-    let start_time = time::Instant::now();
+/// Replays the terminal output, failing on a `cd` to a directory that was
+/// never listed, which would attach the next listings to the wrong one.
+pub fn setup_ssd(inp: &[(usize, Command)]) -> Result<SSD, ParseError> {
     let mut disk = SSD::new();
 
-    for cmd in inp.iter() {
+    for (line, cmd) in inp.iter() {
         match cmd {
-            Command::Cd(dir_name) => disk.exec_cd(dir_name),
+            Command::Cd(dir_name) => {
+                disk.exec_cd(dir_name).map_err(|expected| ParseError {
+                    day: DAY,
+                    line: *line,
+                    // Right after `$ cd `
+                    column: 6,
+                    expected: String::from(expected),
+                    found: format!("`{dir_name}`"),
+                })?
+            }
             Command::Mkdir(dir_name) => disk.exec_mkdir(dir_name),
             Command::Touch(size, file_name) => disk.exec_touch(file_name, *size),
             _ => (),
         };
     }
     // disk.exec_cd(&String::from(ROOT_DIR_NAME));
-
-    let elapsed = start_time.elapsed().as_micros();
-    println!("Elapsed: {}", elapsed);
-    disk
+    Ok(disk)
 }
 
 // Part 1 sol
 pub fn get_cleanable_space(disk: &SSD) -> i32 {
-
     // disk.exec_ls();
 
-    disk
-        .get_all_files()
+    disk.get_all_files()
         .iter()
         .filter(|file| file.is_dir())
         .filter(|file| file.get_size() <= 100000)
@@ -442,11 +450,108 @@ pub fn get_cleanable_space_v2(disk: &SSD) -> i32 {
     let required_total_free_space = 30000000;
     let requried_free_space = required_total_free_space - disk.get_free_space();
 
-    disk
-        .get_all_files()
+    disk.get_all_files()
         .iter()
         .filter(|file| file.is_dir())
         .filter(|file| file.get_size() >= requried_free_space)
         .map(|file| file.get_size())
         .fold(i32::MAX, |acc, curr| if curr < acc { curr } else { acc })
-}
\ No newline at end of file
+}
+
+pub struct NoSpace;
+
+impl Solution for NoSpace {
+    const INPUT_PATH: &'static str = "res/_7_no_space.txt";
+
+    type Input = SSD;
+    type Output = i32;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        setup_ssd(&get_inputs(raw)?)
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        get_cleanable_space(inp)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        get_cleanable_space_v2(inp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_real_input;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn part1_example() {
+        let ssd = setup_ssd(&get_inputs(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(get_cleanable_space(&ssd), 95437);
+    }
+
+    #[test]
+    fn part2_example() {
+        let ssd = setup_ssd(&get_inputs(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(get_cleanable_space_v2(&ssd), 24933642);
+    }
+
+    #[test]
+    fn report_parse_errors() {
+        let err = get_inputs("$ cd /\n$ ls\ndir a\nbig b.txt\n").unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (7, 4, 1));
+        assert_eq!(err.found, "`big b.txt`");
+    }
+
+    #[test]
+    fn report_unknown_directories() {
+        let err = |raw| {
+            setup_ssd(&get_inputs(raw).unwrap())
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            err("$ cd /\n$ cd nowhere\n$ ls\n1 a\n"),
+            "day 7 input, line 2, column 6: expected `/`, `..` or a directory listed in the \
+            current one, found `nowhere`"
+        );
+        assert_eq!(
+            err("$ cd /\n$ ls\ndir a\n$ cd a\n$ cd ..\n$ cd ..\n"),
+            "day 7 input, line 6, column 6: expected `/` or a listed directory, `/` has no \
+            parent, found `..`"
+        );
+    }
+
+    #[test]
+    fn real_input() {
+        let (ans_1, _) = solve_real_input::<NoSpace>();
+        assert_eq!(ans_1, 1325919);
+    }
+}
@@ -1,4 +1,8 @@
-use std::fs;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+const DAY: u8 = 8;
 
 #[derive(Debug)]
 pub struct Quadrent {
@@ -7,29 +11,57 @@ pub struct Quadrent {
     landscape: Vec<i8>,
 }
 
+impl Quadrent {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Height of the tree at column `x` of row `y`.
+    pub fn get(&self, x: usize, y: usize) -> Option<i8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.landscape[y * self.width + x])
+    }
+}
+
+// x: tree size, y: row or column, value: positions of those trees along it
 #[derive(Debug)]
 pub struct ViewMap {
     width: usize,
     height: usize,
-    map: Vec<Option<i32>>,
+    map: Vec<Vec<usize>>,
 }
 
 impl ViewMap {
-    fn get(&self, x: usize, y: usize) -> Option<i32> {
+    fn get(&self, x: usize, y: usize) -> Option<&Vec<usize>> {
         if x >= self.width || y >= self.height {
             return None;
         }
 
         let idx = Coord::new_raw(x, y, self.width, self.height).to_index();
+        self.map.get(idx)
+    }
+}
 
-        if let Some(value) = self.map.get(idx) {
-            match value {
-                Some(map_value) => Some(*map_value),
-                _ => None,
-            }
-        } else {
-            None
-        }
+/// How many trees can be seen from a tree before the view is blocked (or the
+/// edge is reached) in each direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewingDistance {
+    pub up: u32,
+    pub down: u32,
+    pub left: u32,
+    pub right: u32,
+}
+
+impl ViewingDistance {
+    pub fn scenic_score(&self) -> u32 {
+        self.up * self.down * self.left * self.right
     }
 }
 
@@ -44,13 +76,13 @@ struct Coord {
 impl Coord {
     fn new(idx: &usize, width: usize, height: usize) -> Coord {
         let x = idx % width;
-        let y = idx / height;
-        return Coord {
+        let y = idx / width;
+        Coord {
             x,
             y,
             height,
             width,
-        };
+        }
     }
 
     fn new_raw(x: usize, y: usize, width: usize, height: usize) -> Coord {
@@ -115,7 +147,7 @@ impl Coord {
     }
 
     fn to_index(&self) -> usize {
-        self.y * self.height + self.x
+        self.y * self.width + self.x
     }
 }
 
@@ -129,28 +161,40 @@ impl HeatCell {
     }
 }
 
-pub fn get_inputs() -> Quadrent {
-    let input: Vec<String> = fs::read_to_string("res/_8_treetop_tree_house.txt")
-        .unwrap()
-        .split("\r\n")
-        .map(|each| String::from(each))
-        .collect();
+pub fn get_inputs(raw: &str) -> Result<Quadrent, ParseError> {
+    let input = lines(raw);
 
-    let width = input.get(0).unwrap().len();
+    let (_, first_row) = input
+        .first()
+        .ok_or_else(|| ParseError::eof(DAY, 1, "a row of tree heights"))?;
+    let width = first_row.len();
     let height = input.len();
+    let row_expected = format!("a row of {width} trees");
 
     let mut landscape = vec![];
-    for row in input.iter() {
+    for line in input.iter() {
+        let (_, row) = *line;
+        if let Some((idx, chr)) = row.char_indices().find(|(_, chr)| !chr.is_ascii_digit()) {
+            let token = &row[idx..idx + chr.len_utf8()];
+            return Err(ParseError::at(DAY, *line, token, "a tree height digit"));
+        }
+        if row.len() < width {
+            return Err(ParseError::missing(DAY, *line, &row_expected));
+        }
+        if row.len() > width {
+            return Err(ParseError::at(DAY, *line, &row[width..], &row_expected));
+        }
+
         // ASCII 0 to 9 => 48 to 57
-        let mut row_ints: Vec<i8> = row.chars().map(|each| (each as i8) - 48).collect();
+        let mut row_ints: Vec<i8> = row.bytes().map(|each| (each - 48) as i8).collect();
         landscape.append(&mut row_ints);
     }
 
-    Quadrent {
+    Ok(Quadrent {
         width,
         height,
         landscape,
-    }
+    })
 }
 
 // sol 1
@@ -242,50 +286,80 @@ fn get_heat_map(inp: &Quadrent) -> Vec<HeatCell> {
 
 // sol 2
 pub fn get_largest_viewing_scene(inp: &Quadrent) -> u32 {
+    get_viewing_distances(inp)
+        .iter()
+        .map(ViewingDistance::scenic_score)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn get_viewing_distances(inp: &Quadrent) -> Vec<ViewingDistance> {
     let x_view_map = generate_x_axis_view_map(inp);
     let y_view_map = generate_y_axis_view_map(inp);
 
+    let mut viewing_distances = vec![];
     for (idx, cell) in inp.landscape.iter().enumerate() {
         let center = Coord::new(&idx, inp.width, inp.height);
 
+        // Without a tree as tall in the way, the view reaches the edge
+        let up = fetch_larger_tree_index(
+            *cell,
+            &y_view_map,
+            center.x,
+            0,
+            |trees| closest_before(trees, center.y),
+            |tree_idx, selected_tree_idx| tree_idx > selected_tree_idx,
+        );
+
+        let down = fetch_larger_tree_index(
+            *cell,
+            &y_view_map,
+            center.x,
+            inp.height - 1,
+            |trees| closest_after(trees, center.y),
+            |tree_idx, selected_tree_idx| tree_idx < selected_tree_idx,
+        );
+
+        let left = fetch_larger_tree_index(
+            *cell,
+            &x_view_map,
+            center.y,
+            0,
+            |trees| closest_before(trees, center.x),
+            |tree_idx, selected_tree_idx| tree_idx > selected_tree_idx,
+        );
+
         let right = fetch_larger_tree_index(
             *cell,
             &x_view_map,
-            &center,
-            (inp.width - 1 - center.x) as i32,
-            |tree_idx, selected_tree_idx| tree_idx > -1 && tree_idx < selected_tree_idx,
+            center.y,
+            inp.width - 1,
+            |trees| closest_after(trees, center.x),
+            |tree_idx, selected_tree_idx| tree_idx < selected_tree_idx,
         );
 
-        let left = fetch_larger_tree_index(
-            *cell, 
-            &x_view_map, 
-            &center, 
-            () as i32,
-            | tree_idx, selected_tree_idx| tree_idx < 
-        )
+        viewing_distances.push(ViewingDistance {
+            up: (center.y - up) as u32,
+            down: (down - center.y) as u32,
+            left: (center.x - left) as u32,
+            right: (right - center.x) as u32,
+        });
     }
 
-    0
+    viewing_distances
 }
 
-// fn display_view_map(view_map: &ViewMap) {
-//     for (idx, cell) in view_map.map.iter().enumerate() {
-//         if idx % view_map.width == 0 {
-//             println!()
-//         }
-//         print!("{}:{}\t", idx % view_map.width, if cell.is_none() { -1 } else { cell.unwrap() as i32 });
-//     }
-// }
-
 fn fetch_larger_tree_index(
     start_tree_size: i8,
     view_map: &ViewMap,
-    start_coord: &Coord,
-    base_tree_idx: i32,
-    selector_fn: impl Fn(i32, i32) -> bool,
-) -> i32 {
+    line: usize,
+    base_tree_idx: usize,
+    closest_fn: impl Fn(&[usize]) -> Option<usize>,
+    selector_fn: impl Fn(usize, usize) -> bool,
+) -> usize {
     (start_tree_size..10).fold(base_tree_idx, |selected_tree_idx, tree_size| {
-        if let Some(tree_index) = view_map.get(tree_size as usize, start_coord.y) {
+        let trees = view_map.get(tree_size as usize, line);
+        if let Some(tree_index) = trees.and_then(|trees| closest_fn(trees)) {
             if selector_fn(tree_index, selected_tree_idx) {
                 return tree_index;
             }
@@ -294,27 +368,30 @@ fn fetch_larger_tree_index(
     })
 }
 
+fn closest_before(trees: &[usize], idx: usize) -> Option<usize> {
+    let before = trees.partition_point(|tree_idx| *tree_idx < idx);
+    before.checked_sub(1).map(|before| trees[before])
+}
+
+fn closest_after(trees: &[usize], idx: usize) -> Option<usize> {
+    let after = trees.partition_point(|tree_idx| *tree_idx <= idx);
+    trees.get(after).copied()
+}
+
 fn generate_x_axis_view_map(inp: &Quadrent) -> ViewMap {
     let mut x_view_map = ViewMap {
         width: 10,
         height: inp.height,
-        map: vec![None; 10 * inp.height],
+        map: vec![vec![]; 10 * inp.height],
     };
 
     for (idx, cell) in inp.landscape.iter().enumerate() {
-        if idx % inp.width == 0 {
-            // skip the first index as it will be our starting looking cell
-            continue;
-        }
-
         let coord = Coord::new(&idx, inp.width, inp.height);
         let view_map_index = *cell as usize + coord.y * x_view_map.width;
 
-        // We ignore if it has some value, cause from the left,
-        // the repeated value will be furthest
-        if x_view_map.map.get(view_map_index).unwrap().is_none() {
-            x_view_map.map[view_map_index] = Some(idx as i32 % inp.width as i32);
-        }
+        // Walking the landscape from the left keeps every row's
+        // positions in order
+        x_view_map.map[view_map_index].push(coord.x);
     }
 
     x_view_map
@@ -323,25 +400,124 @@ fn generate_x_axis_view_map(inp: &Quadrent) -> ViewMap {
 fn generate_y_axis_view_map(inp: &Quadrent) -> ViewMap {
     let mut y_view_map = ViewMap {
         width: 10,
-        height: inp.height,
-        map: vec![None; 10 * inp.height],
+        height: inp.width,
+        map: vec![vec![]; 10 * inp.width],
     };
 
     for (idx, cell) in inp.landscape.iter().enumerate() {
-        if idx / inp.height == 0 {
-            // skip the first index as it will be our starting looking cell
-            continue;
-        }
-
         let coord = Coord::new(&idx, inp.width, inp.height);
         let view_map_index = *cell as usize + coord.x * y_view_map.width;
 
-        // We ignore if it has some value, cause from the left,
-        // the repeated value will be furthest
-        if y_view_map.map.get(view_map_index).unwrap().is_none() {
-            y_view_map.map[view_map_index] = Some(idx as i32 / inp.height as i32);
-        }
+        // Walking the landscape from the top keeps every column's
+        // positions in order
+        y_view_map.map[view_map_index].push(coord.y);
     }
 
     y_view_map
 }
+
+pub struct TreetopTreeHouse;
+
+impl Solution for TreetopTreeHouse {
+    const INPUT_PATH: &'static str = "res/_8_treetop_tree_house.txt";
+
+    type Input = Quadrent;
+    type Output = u32;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        get_visibility_count(inp)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        get_largest_viewing_scene(inp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn part1_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(get_visibility_count(&inp), 21);
+    }
+
+    #[test]
+    fn report_parse_errors() {
+        let err = |raw| get_inputs(raw).unwrap_err().to_string();
+
+        assert_eq!(
+            err("30373\n25a12\n"),
+            "day 8 input, line 2, column 3: expected a tree height digit, found `a`"
+        );
+        assert_eq!(
+            err("30373\n2551\n"),
+            "day 8 input, line 2, column 5: expected a row of 5 trees, found end of line"
+        );
+        assert_eq!(
+            err("30373\n255120\n"),
+            "day 8 input, line 2, column 6: expected a row of 5 trees, found `0`"
+        );
+        assert_eq!(
+            err(""),
+            "day 8 input, line 1, column 1: expected a row of tree heights, found end of input"
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(get_largest_viewing_scene(&inp), 8);
+    }
+
+    #[test]
+    fn viewing_distances_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        let viewing_distances = get_viewing_distances(&inp);
+
+        // The middle 5 in the second row
+        assert_eq!(
+            viewing_distances[7],
+            ViewingDistance {
+                up: 1,
+                down: 2,
+                left: 1,
+                right: 2,
+            }
+        );
+        // The middle 5 in the fourth row
+        assert_eq!(viewing_distances[17].scenic_score(), 8);
+        // Edge trees see nothing on their edge side
+        assert_eq!(viewing_distances[0].scenic_score(), 0);
+    }
+
+    #[test]
+    fn non_square_landscape() {
+        let inp = get_inputs("3037\n2551\n").unwrap();
+        assert_eq!(get_visibility_count(&inp), 8);
+
+        let viewing_distances = get_viewing_distances(&inp);
+        assert_eq!(
+            viewing_distances[5],
+            ViewingDistance {
+                up: 1,
+                down: 0,
+                left: 1,
+                right: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::Path,
+};
+
+/// Known answers, one `<day> <part> <status> <answer>` per line.
+pub const ANSWERS_PATH: &str = "res/answers.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Accepted,
+    Rejected,
+    TooHigh,
+    TooLow,
+}
+
+impl Status {
+    fn new(status: &str) -> Option<Status> {
+        match status {
+            "accepted" => Some(Status::Accepted),
+            "rejected" => Some(Status::Rejected),
+            "too-high" => Some(Status::TooHigh),
+            "too-low" => Some(Status::TooLow),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Accepted => "accepted",
+            Status::Rejected => "rejected",
+            Status::TooHigh => "too-high",
+            Status::TooLow => "too-low",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub value: String,
+}
+
+/// How a freshly computed answer compares to what is already known.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    KnownWrong,
+    Incorrect { expected: String },
+    New,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::KnownWrong => write!(f, "known wrong"),
+            Verdict::Incorrect { expected } => write!(f, "incorrect, expected {expected}"),
+            Verdict::New => write!(f, "new"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AnswerStore {
+    answers: Vec<Answer>,
+}
+
+impl AnswerStore {
+    pub fn new(raw: &str) -> Result<AnswerStore, String> {
+        let mut answers = vec![];
+
+        for (idx, line) in raw.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let answer = match fields[..] {
+                [day, part, status, value] => day
+                    .parse()
+                    .ok()
+                    .zip(part.parse().ok())
+                    .zip(Status::new(status).map(|status| (status, String::from(value)))),
+                _ => None,
+            };
+            let ((day, part), (status, value)) = answer.ok_or_else(|| {
+                format!(
+                    "line {}: expected `<day> <part> <accepted|rejected|too-high|too-low> <answer>`, found `{line}`",
+                    idx + 1
+                )
+            })?;
+
+            answers.push(Answer {
+                day,
+                part,
+                status,
+                value,
+            });
+        }
+
+        Ok(AnswerStore { answers })
+    }
+
+    /// Loads the store at `path`, starting empty when there is no file yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<AnswerStore, String> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(raw) => AnswerStore::new(&raw).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(err) => Err(format!("unable to read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut raw = String::from("# day part status answer\n");
+        for answer in self.answers.iter() {
+            let _ = writeln!(
+                raw,
+                "{} {} {} {}",
+                answer.day,
+                answer.part,
+                answer.status.as_str(),
+                answer.value
+            );
+        }
+
+        fs::write(path, raw)
+    }
+
+    /// Adds `answer`, replacing what was known about the same value. Refuses
+    /// values that could not be read back once saved.
+    pub fn record(&mut self, answer: Answer) -> Result<(), String> {
+        check_value(&answer.value)?;
+
+        self.answers.retain(|known| {
+            known.day != answer.day || known.part != answer.part || known.value != answer.value
+        });
+        self.answers.push(answer);
+        self.answers.sort_by_key(|answer| (answer.day, answer.part));
+        Ok(())
+    }
+
+    pub fn answers(&self, day: u8, part: u8) -> impl Iterator<Item = &Answer> {
+        self.answers
+            .iter()
+            .filter(move |answer| answer.day == day && answer.part == part)
+    }
+
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers(day, part)
+            .find(|answer| answer.status == Status::Accepted)
+            .map(|answer| answer.value.as_str())
+    }
+
+    pub fn verify(&self, day: u8, part: u8, value: &str) -> Verdict {
+        if let Some(expected) = self.accepted(day, part) {
+            if expected == value {
+                return Verdict::Correct;
+            }
+            return Verdict::Incorrect {
+                expected: String::from(expected),
+            };
+        }
+
+        let is_rejected = self
+            .answers(day, part)
+            .any(|answer| answer.status != Status::Accepted && answer.value == value);
+        if is_rejected {
+            return Verdict::KnownWrong;
+        }
+
+        Verdict::New
+    }
+}
+
+/// Answers are saved as a single whitespace separated field.
+pub fn check_value(value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(String::from("the answer is empty"));
+    }
+    if value.contains(char::is_whitespace) {
+        return Err(format!("the answer `{value}` contains whitespace"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "\
+# day part status answer
+1 1 accepted 71934
+1 2 rejected 211447
+";
+
+    #[test]
+    fn verify_against_known_answers() {
+        let store = AnswerStore::new(ANSWERS).unwrap();
+
+        assert_eq!(store.verify(1, 1, "71934"), Verdict::Correct);
+        assert_eq!(
+            store.verify(1, 1, "70000"),
+            Verdict::Incorrect {
+                expected: String::from("71934")
+            }
+        );
+        assert_eq!(store.verify(1, 2, "211447"), Verdict::KnownWrong);
+        assert_eq!(store.verify(1, 2, "200000"), Verdict::New);
+    }
+
+    #[test]
+    fn record_and_save_round_trip() {
+        let mut store = AnswerStore::new(ANSWERS).unwrap();
+        store
+            .record(Answer {
+                day: 1,
+                part: 2,
+                status: Status::TooLow,
+                value: String::from("200000"),
+            })
+            .unwrap();
+        store
+            .record(Answer {
+                day: 1,
+                part: 1,
+                status: Status::Accepted,
+                value: String::from("71934"),
+            })
+            .unwrap();
+        for value in ["", "71 934"] {
+            let answer = Answer {
+                day: 1,
+                part: 1,
+                status: Status::Rejected,
+                value: String::from(value),
+            };
+            assert!(store.record(answer).is_err());
+        }
+
+        let path = std::env::temp_dir().join(format!("answers_{}.txt", std::process::id()));
+        store.save(&path).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            saved,
+            "\
+# day part status answer
+1 1 accepted 71934
+1 2 rejected 211447
+1 2 too-low 200000
+"
+        );
+        assert_eq!(
+            AnswerStore::new(&saved).unwrap().verify(1, 2, "200000"),
+            Verdict::KnownWrong
+        );
+    }
+
+    #[test]
+    fn reject_malformed_lines() {
+        let err = AnswerStore::new("1 1 maybe 71934").unwrap_err();
+        assert!(err.starts_with("line 1:"), "{err}");
+    }
+}
@@ -0,0 +1,225 @@
+use std::{fmt::Write, fs, io, path::Path, time::Duration};
+
+/// Where bench results are kept to compare the next run against.
+pub const BENCH_PATH: &str = "target/bench.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    /// Summarises the samples, of which there must be at least one.
+    pub fn new(mut samples: Vec<Duration>) -> Timing {
+        samples.sort();
+
+        Timing {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings of each phase of a day, over many runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Bench {
+    pub parse: Timing,
+    pub part1: Timing,
+    pub part2: Timing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub phase: String,
+    pub timing: Timing,
+}
+
+impl Entry {
+    fn new(fields: &[&str]) -> Option<Entry> {
+        let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+        match fields {
+            [day, phase, min, median, max] => Some(Entry {
+                day: day.parse().ok()?,
+                phase: String::from(*phase),
+                timing: Timing {
+                    min: nanos(min)?,
+                    median: nanos(median)?,
+                    max: nanos(max)?,
+                },
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Bench results, saved as one `<day> <phase> <min> <median> <max>` line per
+/// phase with times in nanoseconds.
+#[derive(Debug, Default)]
+pub struct BenchReport {
+    entries: Vec<Entry>,
+}
+
+impl BenchReport {
+    pub fn new(raw: &str) -> Result<BenchReport, String> {
+        let mut entries = vec![];
+
+        for (idx, line) in raw.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let entry = Entry::new(&fields);
+
+            entries.push(entry.ok_or_else(|| {
+                format!(
+                    "line {}: expected `<day> <phase> <min> <median> <max>`, found `{line}`",
+                    idx + 1
+                )
+            })?);
+        }
+
+        Ok(BenchReport { entries })
+    }
+
+    /// Loads the report at `path`, starting empty when there is no file yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<BenchReport, String> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(raw) => BenchReport::new(&raw).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(BenchReport::default()),
+            Err(err) => Err(format!("unable to read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut raw = String::from("# day phase min_ns median_ns max_ns\n");
+        for entry in self.entries.iter() {
+            let timing = entry.timing;
+            // Writing to a String never fails
+            let _ = writeln!(
+                raw,
+                "{} {} {} {} {}",
+                entry.day,
+                entry.phase,
+                timing.min.as_nanos(),
+                timing.median.as_nanos(),
+                timing.max.as_nanos()
+            );
+        }
+        fs::write(path, raw)
+    }
+
+    pub fn push(&mut self, day: u8, bench: &Bench) {
+        for (phase, timing) in [
+            ("parse", bench.parse),
+            ("part1", bench.part1),
+            ("part2", bench.part2),
+        ] {
+            self.entries.push(Entry {
+                day,
+                phase: String::from(phase),
+                timing,
+            });
+        }
+    }
+
+    /// Replaces the entries of every day found in `latest`.
+    pub fn update(&mut self, latest: &BenchReport) {
+        self.entries
+            .retain(|entry| !latest.entries.iter().any(|each| each.day == entry.day));
+        self.entries.extend(latest.entries.iter().cloned());
+        self.entries.sort_by_key(|entry| entry.day);
+    }
+
+    pub fn get(&self, day: u8, phase: &str) -> Option<&Timing> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.phase == phase)
+            .map(|entry| &entry.timing)
+    }
+
+    /// Renders the report, with each median's change against `previous`.
+    pub fn table(&self, previous: &BenchReport) -> String {
+        let mut table = format!(
+            "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}\n",
+            "day", "phase", "min", "median", "max", "vs last"
+        );
+
+        for entry in self.entries.iter() {
+            let timing = entry.timing;
+            let change = match previous.get(entry.day, &entry.phase) {
+                Some(prev) if !prev.median.is_zero() => {
+                    let ratio = timing.median.as_secs_f64() / prev.median.as_secs_f64();
+                    format!("{:+.1}%", (ratio - 1.0) * 100.0)
+                }
+                _ => String::from("-"),
+            };
+
+            let _ = writeln!(
+                table,
+                "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}",
+                entry.day,
+                entry.phase,
+                format!("{:.1?}", timing.min),
+                format!("{:.1?}", timing.median),
+                format!("{:.1?}", timing.max),
+                change
+            );
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(min: u64, median: u64, max: u64) -> Timing {
+        Timing {
+            min: Duration::from_micros(min),
+            median: Duration::from_micros(median),
+            max: Duration::from_micros(max),
+        }
+    }
+
+    #[test]
+    fn timing_summarises_samples() {
+        let samples = [40, 10, 30, 20, 50].map(Duration::from_micros).to_vec();
+        assert_eq!(Timing::new(samples), micros(10, 30, 50));
+    }
+
+    #[test]
+    fn report_round_trips_through_text() {
+        let mut report = BenchReport::default();
+        report.push(
+            7,
+            &Bench {
+                parse: micros(1, 2, 3),
+                part1: micros(4, 5, 6),
+                part2: micros(7, 8, 9),
+            },
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("advent_of_code_bench_{}.txt", std::process::id()));
+        report.save(&path).unwrap();
+        let loaded = BenchReport::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.entries, report.entries);
+        assert_eq!(loaded.get(7, "part1"), Some(&micros(4, 5, 6)));
+    }
+}
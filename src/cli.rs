@@ -0,0 +1,317 @@
+use std::{path::PathBuf, str::FromStr};
+
+use advent_of_code::bench::BENCH_PATH;
+use advent_of_code::input::InputSource;
+
+pub const USAGE: &str = "\
+Usage: advent_of_code run --day <N> [--part <1|2>] [--input <PATH>]
+       advent_of_code run-all
+       advent_of_code bench [--day <N>] [--runs <N>] [--out <PATH>]
+       advent_of_code fetch [--day <N>]
+       advent_of_code submit --day <N> --part <1|2> [--answer <VALUE>]
+       advent_of_code new-day --day <N> --name <NAME>
+
+Options:
+  -d, --day <N>       puzzle day to run, bench and fetch every day when omitted
+  -p, --part <N>      puzzle part to run, runs both parts when omitted
+  -i, --input <PATH>  puzzle input file, `-` for stdin, defaults to the day's res/ file
+  -r, --runs <N>      bench repetitions of each phase, defaults to 10
+  -o, --out <PATH>    bench results file to compare against and replace,
+                      defaults to target/bench.txt
+  -a, --answer <VAL>  answer to submit, defaults to solving the day's res/ input
+  -n, --name <NAME>   puzzle title of the day to scaffold, e.g. `Rope Bridge`
+
+Missing or empty res/ inputs are downloaded, and answers submitted, with the
+session token in AOC_SESSION to AOC_BASE_URL (defaults to
+https://adventofcode.com/2022).";
+
+const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        day: u8,
+        part: Option<u8>,
+        input: Option<InputSource>,
+    },
+    RunAll,
+    Bench {
+        day: Option<u8>,
+        runs: usize,
+        out: PathBuf,
+    },
+    Fetch {
+        day: Option<u8>,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+    },
+    NewDay {
+        day: u8,
+        name: String,
+    },
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Command, String> {
+        let mut args = args.iter();
+
+        match args.next().map(|each| each.as_str()) {
+            Some("run") => Command::parse_run(args),
+            Some("run-all") => match args.next() {
+                Some(other) => Err(format!("unknown option `{other}`")),
+                None => Ok(Command::RunAll),
+            },
+            Some("bench") => Command::parse_bench(args),
+            Some("fetch") => Command::parse_fetch(args),
+            Some("submit") => Command::parse_submit(args),
+            Some("new-day") => Command::parse_new_day(args),
+            Some(other) => Err(format!("unknown command `{other}`")),
+            None => Err(String::from("missing command")),
+        }
+    }
+
+    fn parse_run<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "-d" | "--day" => day = Some(parse_number(flag, args.next())?),
+                "-p" | "--part" => part = Some(parse_number(flag, args.next())?),
+                "-i" | "--input" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{flag}`"))?;
+                    input = Some(InputSource::new(path));
+                }
+                other => return Err(format!("unknown option `{other}`")),
+            }
+        }
+
+        let day = day.ok_or_else(|| String::from("missing `--day`"))?;
+        if let Some(part) = part {
+            if part != 1 && part != 2 {
+                return Err(format!("part must be 1 or 2, got {part}"));
+            }
+        }
+
+        Ok(Command::Run { day, part, input })
+    }
+
+    fn parse_bench<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+        let mut day = None;
+        let mut runs = DEFAULT_RUNS;
+        let mut out = PathBuf::from(BENCH_PATH);
+
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "-d" | "--day" => day = Some(parse_number(flag, args.next())?),
+                "-r" | "--runs" => runs = parse_number(flag, args.next())?,
+                "-o" | "--out" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{flag}`"))?;
+                    out = PathBuf::from(path);
+                }
+                other => return Err(format!("unknown option `{other}`")),
+            }
+        }
+
+        if runs == 0 {
+            return Err(String::from("runs must be at least 1"));
+        }
+
+        Ok(Command::Bench { day, runs, out })
+    }
+
+    fn parse_fetch<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+        let mut day = None;
+
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "-d" | "--day" => day = Some(parse_number(flag, args.next())?),
+                other => return Err(format!("unknown option `{other}`")),
+            }
+        }
+
+        Ok(Command::Fetch { day })
+    }
+
+    fn parse_submit<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+        let mut day = None;
+        let mut part = None;
+        let mut answer = None;
+
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "-d" | "--day" => day = Some(parse_number(flag, args.next())?),
+                "-p" | "--part" => part = Some(parse_number(flag, args.next())?),
+                "-a" | "--answer" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{flag}`"))?;
+                    answer = Some(value.clone());
+                }
+                other => return Err(format!("unknown option `{other}`")),
+            }
+        }
+
+        let day = day.ok_or_else(|| String::from("missing `--day`"))?;
+        let part = part.ok_or_else(|| String::from("missing `--part`"))?;
+        if part != 1 && part != 2 {
+            return Err(format!("part must be 1 or 2, got {part}"));
+        }
+
+        Ok(Command::Submit { day, part, answer })
+    }
+
+    fn parse_new_day<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+        let mut day = None;
+        let mut name = None;
+
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "-d" | "--day" => day = Some(parse_number(flag, args.next())?),
+                "-n" | "--name" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{flag}`"))?;
+                    name = Some(value.clone());
+                }
+                other => return Err(format!("unknown option `{other}`")),
+            }
+        }
+
+        let day = day.ok_or_else(|| String::from("missing `--day`"))?;
+        let name = name.ok_or_else(|| String::from("missing `--name`"))?;
+
+        Ok(Command::NewDay { day, name })
+    }
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing value for `{flag}`"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{flag}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(argv: &str) -> Result<Command, String> {
+        let args: Vec<String> = argv.split_whitespace().map(String::from).collect();
+        Command::parse(&args)
+    }
+
+    #[test]
+    fn parse_valid_commands() {
+        let cases = [
+            (
+                "run --day 3",
+                Command::Run {
+                    day: 3,
+                    part: None,
+                    input: None,
+                },
+            ),
+            (
+                "run -d 5 -p 2 -i -",
+                Command::Run {
+                    day: 5,
+                    part: Some(2),
+                    input: Some(InputSource::Stdin),
+                },
+            ),
+            (
+                "run --input res/day.txt --day 1",
+                Command::Run {
+                    day: 1,
+                    part: None,
+                    input: Some(InputSource::File(PathBuf::from("res/day.txt"))),
+                },
+            ),
+            ("run-all", Command::RunAll),
+            (
+                "bench",
+                Command::Bench {
+                    day: None,
+                    runs: DEFAULT_RUNS,
+                    out: PathBuf::from(BENCH_PATH),
+                },
+            ),
+            (
+                "bench -d 8 --runs 3 -o out.txt",
+                Command::Bench {
+                    day: Some(8),
+                    runs: 3,
+                    out: PathBuf::from("out.txt"),
+                },
+            ),
+            ("fetch", Command::Fetch { day: None }),
+            ("fetch --day 2", Command::Fetch { day: Some(2) }),
+            (
+                "submit -d 1 -p 2",
+                Command::Submit {
+                    day: 1,
+                    part: 2,
+                    answer: None,
+                },
+            ),
+            (
+                "submit --day 1 --part 1 --answer 71934",
+                Command::Submit {
+                    day: 1,
+                    part: 1,
+                    answer: Some(String::from("71934")),
+                },
+            ),
+            (
+                "new-day -d 9 -n rope_bridge",
+                Command::NewDay {
+                    day: 9,
+                    name: String::from("rope_bridge"),
+                },
+            ),
+        ];
+
+        for (argv, expected) in cases {
+            assert_eq!(parse(argv), Ok(expected), "{argv}");
+        }
+    }
+
+    #[test]
+    fn parse_invalid_commands() {
+        let cases = [
+            ("", "missing command"),
+            ("walk", "unknown command `walk`"),
+            ("run", "missing `--day`"),
+            ("run --day", "missing value for `--day`"),
+            ("run -d three", "invalid value `three` for `-d`"),
+            ("run -d 1 -p 3", "part must be 1 or 2, got 3"),
+            ("run -d 1 --input", "missing value for `--input`"),
+            ("run -d 1 --verbose", "unknown option `--verbose`"),
+            ("run-all -d 1", "unknown option `-d`"),
+            ("bench --runs 0", "runs must be at least 1"),
+            ("bench --runs -1", "invalid value `-1` for `--runs`"),
+            ("bench -o", "missing value for `-o`"),
+            ("fetch --part 1", "unknown option `--part`"),
+            ("fetch -d 300", "invalid value `300` for `-d`"),
+            ("submit -p 1", "missing `--day`"),
+            ("submit -d 1", "missing `--part`"),
+            ("submit -d 1 -p 0", "part must be 1 or 2, got 0"),
+            ("submit -d 1 -p 1 -a", "missing value for `-a`"),
+            ("new-day -n rope_bridge", "missing `--day`"),
+            ("new-day -d 9", "missing `--name`"),
+        ];
+
+        for (argv, expected) in cases {
+            assert_eq!(parse(argv), Err(String::from(expected)), "{argv}");
+        }
+    }
+}
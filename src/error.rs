@@ -0,0 +1,104 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::input::Line;
+
+/// Where and why a day's puzzle input could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// Points at `token`, a slice of the line's text. A token that was copied
+    /// out of the line is looked up by its text instead, and one that is not
+    /// in the line at all points at the start of the line.
+    pub fn at(day: u8, (line, text): Line, token: &str, expected: &str) -> ParseError {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|offset| offset + token.len() <= text.len())
+            .or_else(|| text.find(token))
+            .unwrap_or(0);
+
+        ParseError {
+            day,
+            line,
+            column: offset + 1,
+            expected: String::from(expected),
+            found: format!("`{token}`"),
+        }
+    }
+
+    /// Points just past the end of a line that stopped before `expected`.
+    pub fn missing(day: u8, (line, text): Line, expected: &str) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column: text.len() + 1,
+            expected: String::from(expected),
+            found: String::from("end of line"),
+        }
+    }
+
+    /// For input that ends before a whole section shows up.
+    pub fn eof(day: u8, line: usize, expected: &str) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column: 1,
+            expected: String::from(expected),
+            found: String::from("end of input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of the line's text, reporting its position on failure.
+pub fn parse_token<T: FromStr>(
+    day: u8,
+    line: Line,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(day, line, token, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_at_tokens() {
+        let line = (4, "move 1 from 2 to 1");
+
+        let err = ParseError::at(5, line, &line.1[12..13], "a stack");
+        assert_eq!(
+            err.to_string(),
+            "day 5 input, line 4, column 13: expected a stack, found `2`"
+        );
+
+        // Not a slice of the line, but the same text
+        let copied = String::from("from");
+        assert_eq!(ParseError::at(5, line, &copied, "`from`").column, 8);
+        assert_eq!(ParseError::at(5, line, "form", "`from`").column, 1);
+
+        let err = ParseError::missing(5, line, "end of line");
+        assert_eq!((err.column, err.found.as_str()), (19, "end of line"));
+    }
+}
@@ -0,0 +1,243 @@
+use std::{env, fs, io, path::Path, time::Duration};
+
+/// Where puzzle inputs are downloaded from unless `AOC_BASE_URL` says otherwise.
+pub const BASE_URL: &str = "https://adventofcode.com/2022";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Talks to the puzzle site with a session token: inputs are downloaded from
+/// `<base_url>/day/<day>/input` and answers posted to `<base_url>/day/<day>/answer`.
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str) -> Fetcher {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(TIMEOUT)
+            .user_agent("advent_of_code input fetcher")
+            .build();
+
+        Fetcher {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session: String::from(session.trim()),
+            agent,
+        }
+    }
+
+    /// A fetcher configured from `AOC_SESSION` and `AOC_BASE_URL`, or `None`
+    /// when there is no session token to download with.
+    pub fn from_env() -> Option<Fetcher> {
+        let session = env::var(SESSION_ENV).ok()?;
+        if session.trim().is_empty() {
+            return None;
+        }
+
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| String::from(BASE_URL));
+        Some(Fetcher::new(&base_url, &session))
+    }
+
+    pub fn fetch(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/day/{day}/input", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(code, _) => format!("{url} answered with status {code}"),
+                ureq::Error::Transport(err) => format!("unable to reach {url}: {err}"),
+            })?;
+
+        let raw = response
+            .into_string()
+            .map_err(|err| format!("unable to read the response from {url}: {err}"))?;
+        if raw.trim().is_empty() {
+            return Err(format!("{url} answered with an empty input"));
+        }
+
+        Ok(raw)
+    }
+
+    /// Posts `value` as the answer to `part` of `day`, returning the reply page.
+    pub fn submit(&self, day: u8, part: u8, value: &str) -> Result<String, String> {
+        let url = format!("{}/day/{day}/answer", self.base_url);
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", value)])
+            .map_err(|err| match err {
+                ureq::Error::Status(code, _) => format!("{url} answered with status {code}"),
+                ureq::Error::Transport(err) => format!("unable to reach {url}: {err}"),
+            })?;
+
+        response
+            .into_string()
+            .map_err(|err| format!("unable to read the response from {url}: {err}"))
+    }
+}
+
+/// Reads the day's input cached at `path`. A missing or empty file is first
+/// downloaded with `fetcher` and written to `path`.
+pub fn resolve(
+    path: impl AsRef<Path>,
+    day: u8,
+    fetcher: Option<&Fetcher>,
+) -> Result<String, String> {
+    let path = path.as_ref();
+
+    let missing = match fs::read_to_string(path) {
+        Ok(raw) if !raw.trim().is_empty() => return Ok(raw),
+        Ok(_) => "is empty",
+        Err(err) if err.kind() == io::ErrorKind::NotFound => "does not exist",
+        Err(err) => return Err(format!("unable to read {}: {err}", path.display())),
+    };
+
+    let Some(fetcher) = fetcher else {
+        return Err(format!(
+            "{} {missing}, set {SESSION_ENV} to download it",
+            path.display()
+        ));
+    };
+    let raw = fetcher.fetch(day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("unable to create {}: {err}", dir.display()))?;
+    }
+    fs::write(path, &raw).map_err(|err| format!("unable to write {}: {err}", path.display()))?;
+
+    Ok(raw)
+}
+
+/// Serves a single request on a local port with `status` and `body`. The
+/// returned base URL points at it, and the handle hands back the request line,
+/// the headers and, last, the request body.
+#[cfg(test)]
+pub fn mock_server(
+    status: &'static str,
+    body: &'static str,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/2022", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut request = vec![];
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            request.push(String::from(line));
+        }
+
+        let content_length = request
+            .iter()
+            .find_map(|header| header.strip_prefix("Content-Length: "))
+            .map_or(0, |len| len.parse().unwrap());
+        let mut content = vec![0; content_length];
+        reader.read_exact(&mut content).unwrap();
+        request.push(String::from_utf8(content).unwrap());
+
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request
+    });
+
+    (base_url, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent_of_code_fetch_{}", std::process::id()));
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn download_and_cache_missing_input() {
+        let (base_url, server) = mock_server("200 OK", "1000\n2000\n");
+        let fetcher = Fetcher::new(&base_url, "secret");
+        let path = temp_path("_1_calorie_elf.txt");
+
+        assert_eq!(
+            resolve(&path, 1, Some(&fetcher)),
+            Ok(String::from("1000\n2000\n"))
+        );
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2022/day/1/input HTTP/1.1");
+        assert!(request.contains(&String::from("Cookie: session=secret")));
+
+        // Served from the cache from now on, the mock server is gone
+        assert_eq!(
+            resolve(&path, 1, Some(&fetcher)),
+            Ok(String::from("1000\n2000\n"))
+        );
+    }
+
+    #[test]
+    fn download_over_empty_input() {
+        let (base_url, server) = mock_server("200 OK", "mjqjpqmgbljsphdztnv\n");
+        let fetcher = Fetcher::new(&base_url, "secret");
+        let path = temp_path("_6_tuning_trouble.txt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "\n").unwrap();
+
+        assert_eq!(resolve(&path, 6, None).map_err(|_| ()), Err(()));
+        assert_eq!(
+            resolve(&path, 6, Some(&fetcher)),
+            Ok(String::from("mjqjpqmgbljsphdztnv\n"))
+        );
+        assert_eq!(server.join().unwrap()[0], "GET /2022/day/6/input HTTP/1.1");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mjqjpqmgbljsphdztnv\n");
+    }
+
+    #[test]
+    fn report_failed_downloads() {
+        let (base_url, server) = mock_server("400 Bad Request", "Puzzle inputs differ by user.");
+        let fetcher = Fetcher::new(&base_url, "expired");
+        let path = temp_path("_2_rock_paper_sissor.txt");
+
+        assert_eq!(
+            resolve(&path, 2, Some(&fetcher)),
+            Err(format!("{base_url}/day/2/input answered with status 400"))
+        );
+        server.join().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn report_unreadable_input_without_downloading() {
+        // Nothing listens there, so a download attempt would fail differently
+        let fetcher = Fetcher::new("http://127.0.0.1:9", "secret");
+        let path = temp_path("_3_rucksack_reorg.txt");
+        fs::create_dir_all(&path).unwrap();
+
+        let err = resolve(&path, 3, Some(&fetcher)).unwrap_err();
+        assert!(err.starts_with("unable to read "), "{err}");
+        fs::remove_dir(&path).unwrap();
+    }
+}
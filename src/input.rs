@@ -0,0 +1,107 @@
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// Where a day's raw puzzle input is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// `-` reads from stdin, anything else is taken as a file path.
+    pub fn new(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => read_all(io::stdin().lock()),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Drains any reader into the raw input string the day parsers take.
+pub fn read_all(mut reader: impl Read) -> io::Result<String> {
+    let mut raw = String::new();
+    reader.read_to_string(&mut raw)?;
+    Ok(raw)
+}
+
+/// A line of raw input with its 1-based line number.
+pub type Line<'a> = (usize, &'a str);
+
+/// Splits raw input into numbered lines, accepting both `\n` and `\r\n`
+/// endings and dropping any blank lines left at the end of the input.
+pub fn lines(raw: &str) -> Vec<Line<'_>> {
+    let mut lines: Vec<Line> = raw
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| (idx + 1, text))
+        .collect();
+
+    while lines.last().is_some_and(|(_, text)| text.trim().is_empty()) {
+        lines.pop();
+    }
+
+    lines
+}
+
+/// Splits raw input into blank line separated groups of numbered lines.
+pub fn paragraphs(raw: &str) -> Vec<Vec<Line<'_>>> {
+    let mut paragraphs = vec![];
+    let mut paragraph = vec![];
+
+    for line in lines(raw) {
+        if !line.1.trim().is_empty() {
+            paragraph.push(line);
+        } else if !paragraph.is_empty() {
+            paragraphs.push(paragraph);
+            paragraph = vec![];
+        }
+    }
+
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph);
+    }
+
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_ignore_line_endings_and_trailing_blanks() {
+        let unix = lines("A Y\nB X\n\n");
+        let windows = lines("A Y\r\nB X\r\n\r\n");
+        assert_eq!(unix, vec![(1, "A Y"), (2, "B X")]);
+        assert_eq!(unix, windows);
+    }
+
+    #[test]
+    fn paragraphs_split_on_blank_lines() {
+        let groups = paragraphs("1000\r\n2000\r\n\r\n4000\r\n\r\n");
+        assert_eq!(
+            groups,
+            vec![vec![(1, "1000"), (2, "2000")], vec![(4, "4000")]]
+        );
+    }
+}
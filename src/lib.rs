@@ -0,0 +1,26 @@
+//! Advent of Code 2022 solutions.
+//!
+//! Every day lives in its own module with its parser (`get_inputs`), its
+//! domain types and its solvers, and implements [`Solution`]. The [`runner`]
+//! picks days at runtime and is what the `advent_of_code` binary is built on.
+
+pub mod _1_calorie_elf;
+pub mod _2_rock_paper_sissor;
+pub mod _3_rucksack_reorg;
+pub mod _4_camp_cleanup;
+pub mod _5_supply_stacks;
+pub mod _6_tuning_trouble;
+pub mod _7_no_space;
+pub mod _8_treetop_tree_house;
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod fetch;
+pub mod input;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+
+pub use error::ParseError;
+pub use solution::Solution;
@@ -1,66 +1,156 @@
-// mod _1_calorie_elf;
-// mod _2_rock_paper_sissor;
-// mod _3_rucksack_reorg;
-// mod _4_camp_cleanup;
-// mod _5_supply_stacks;
-// mod _6_tuning_trouble;
-// mod _7_no_space;
-mod _8_treetop_tree_house;
-
-// use crate::_1_calorie_elf::*;
-// use crate::_2_rock_paper_sissor::*;
-// use crate::_3_rucksack_reorg::*;
-// use crate::_4_camp_cleanup::*;
-// use crate::_5_supply_stacks::*;
-// use crate::_6_tuning_trouble::*;
-// use crate::_7_no_space::*;
-use crate::_8_treetop_tree_house::*;
+mod cli;
+
+use std::{env, process, time::SystemTime};
+
+use advent_of_code::answers::{AnswerStore, ANSWERS_PATH};
+use advent_of_code::bench::BenchReport;
+use advent_of_code::fetch::{Fetcher, SESSION_ENV};
+use advent_of_code::runner;
+use advent_of_code::scaffold::NewDay;
+use advent_of_code::submit::{self, COOLDOWN_PATH};
+
+use crate::cli::{Command, USAGE};
 
 fn main() {
-    let inp = get_inputs();
-
-    // # _1_calori_elf
-    // 71934 211447(X)
-    // let ans_1 = get_elf_with_most_calories(&inp);
-    // let ans_2 = get_top_three_elfs_with_most_calories(&inp);
-
-    // # _2_rock_paper_sissor
-    // 9177 12111
-    // let ans_1 = score(&inp);
-    // let ans_2 = score_v2(&inp);
-
-    // # _3_rucksack_reorg
-    // 7553 2758
-    // let ans_1 = reorg(&inp);
-    // let ans_2 = stick_sticker(&inp);
-
-    // # _4_camp_cleanup
-    // 509 870
-    // let ans_1 = get_highest_recommendation(inp);
-    // let ans_1 = get_overlaps(inp);
-
-    // # _5_supply_stacks
-    // TWSGQHNHL JNRSCDWPP
-    // let ans_1 = rearrange(&mut inp);
-    // let ans_1 = rearrange_v2(&mut inp);
-
-    // # _6_tuning_trouble
-    // 1723 3708
-    // let ans_1 = find_index(inp, 4);
-    // let ans_1 = find_index(inp, 14);
-
-    // # _7_no_space
-    // Elapsed: 231
-    // 1325919
-    // let ssd = setup_ssd(&inp);
-    // let ans_1 = get_cleanable_space(&ssd);
-    // let ans_2 = get_cleanable_space_v2(&ssd);
-
-    // # _8_treetop_tree_house
-    //
-    // let ans_1 = get_visibility_count(&inp);
-    let ans_2 = get_largest_viewing_scene(&inp);
-
-    // print!("{ans_1}");
-    println!(" {ans_2}");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let cmd = match Command::parse(&args) {
+        Ok(cmd) => cmd,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    match cmd {
+        Command::Run { day, part, input } => {
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![1, 2],
+            };
+
+            let store = AnswerStore::load(ANSWERS_PATH).unwrap_or_else(|err| exit_with(&err));
+            let answers =
+                runner::run(day, &parts, input.as_ref()).unwrap_or_else(|err| exit_with(&err));
+
+            for (part, ans) in parts.iter().zip(answers) {
+                // Known answers only hold for our own puzzle input
+                if input.is_some() {
+                    println!("Day {day} part {part}: {ans}");
+                } else {
+                    let verdict = store.verify(day, *part, &ans);
+                    println!("Day {day} part {part}: {ans} ({verdict})");
+                }
+            }
+        }
+        Command::RunAll => {
+            let store = AnswerStore::load(ANSWERS_PATH).unwrap_or_else(|err| exit_with(&err));
+            let rows = runner::run_all();
+
+            print!("{}", runner::summary(&rows, &store));
+            if rows
+                .iter()
+                .any(|row| matches!(row.outcome, runner::Outcome::Failed(_)))
+            {
+                process::exit(1);
+            }
+        }
+        Command::Bench { day, runs, out } => {
+            let days: Vec<u8> = match day {
+                Some(day) => vec![day],
+                None => runner::DAYS.collect(),
+            };
+
+            let previous = BenchReport::load(&out).unwrap_or_else(|err| exit_with(&err));
+            let mut report = BenchReport::default();
+            let mut failed = vec![];
+            for day in days {
+                match runner::bench(day, runs) {
+                    Ok(bench) => report.push(day, &bench),
+                    Err(err) => failed.push(format!("day {day}: {err}")),
+                }
+            }
+
+            print!("{}", report.table(&previous));
+            for err in failed.iter() {
+                eprintln!("error: {err}");
+            }
+
+            // Keep the last results of the days that were not benched this time
+            let mut saved = previous;
+            saved.update(&report);
+            if let Err(err) = saved.save(&out) {
+                exit_with(&format!("unable to write {}: {err}", out.display()));
+            }
+            if !failed.is_empty() {
+                process::exit(1);
+            }
+        }
+        Command::Fetch { day } => {
+            let days: Vec<u8> = match day {
+                Some(day) => vec![day],
+                None => runner::DAYS.collect(),
+            };
+
+            for day in days {
+                let puzzle = runner::puzzle(day)
+                    .unwrap_or_else(|| exit_with(&format!("day {day} is not implemented")));
+                let raw = runner::read_input(puzzle.as_ref(), day, None)
+                    .unwrap_or_else(|err| exit_with(&err));
+                println!(
+                    "Day {day}: {} ({} lines)",
+                    puzzle.input_path(),
+                    raw.lines().count()
+                );
+            }
+        }
+        Command::Submit { day, part, answer } => {
+            let fetcher = Fetcher::from_env()
+                .unwrap_or_else(|| exit_with(&format!("set {SESSION_ENV} to submit answers")));
+            let answer = match answer {
+                Some(answer) => answer,
+                None => runner::run(day, &[part], None)
+                    .unwrap_or_else(|err| exit_with(&err))
+                    .remove(0),
+            };
+
+            let mut store = AnswerStore::load(ANSWERS_PATH).unwrap_or_else(|err| exit_with(&err));
+            let reply = submit::submit(
+                &fetcher,
+                &mut store,
+                COOLDOWN_PATH,
+                (day, part, &answer),
+                SystemTime::now(),
+            )
+            .unwrap_or_else(|err| exit_with(&err));
+
+            match reply.wait {
+                Some(wait) => println!(
+                    "Day {day} part {part}: {answer} ({}, wait {}s)",
+                    reply.feedback,
+                    wait.as_secs()
+                ),
+                None => println!("Day {day} part {part}: {answer} ({})", reply.feedback),
+            }
+
+            if let Err(err) = store.save(ANSWERS_PATH) {
+                exit_with(&format!("unable to write {ANSWERS_PATH}: {err}"));
+            }
+        }
+        Command::NewDay { day, name } => {
+            let new_day = NewDay::new(day, &name).unwrap_or_else(|err| exit_with(&err));
+            let created = new_day.create(".").unwrap_or_else(|err| exit_with(&err));
+
+            for path in created {
+                println!("Created {path}");
+            }
+            println!("Registered {} as day {day}", new_day.struct_name());
+            println!("Add it to `DAYS` in src/runner.rs once solved");
+        }
+    }
+}
+
+fn exit_with(err: &str) -> ! {
+    eprintln!("error: {err}");
+    process::exit(1);
 }
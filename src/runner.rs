@@ -0,0 +1,324 @@
+use std::{
+    any::Any,
+    fmt::Write,
+    hint::black_box,
+    marker::PhantomData,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::_1_calorie_elf::CalorieElf;
+use crate::_2_rock_paper_sissor::RockPaperSissor;
+use crate::_3_rucksack_reorg::RucksackReorg;
+use crate::_4_camp_cleanup::CampCleanup;
+use crate::_5_supply_stacks::SupplyStacks;
+use crate::_6_tuning_trouble::TuningTrouble;
+use crate::_7_no_space::NoSpace;
+use crate::_8_treetop_tree_house::TreetopTreeHouse;
+use crate::answers::AnswerStore;
+use crate::bench::{Bench, Timing};
+use crate::error::ParseError;
+use crate::fetch::{self, Fetcher};
+use crate::input::InputSource;
+use crate::solution::Solution;
+
+/// Every day with a solution. Days scaffolded by `new-day` only join once
+/// solved, they can be run one at a time until then.
+pub const DAYS: RangeInclusive<u8> = 1..=8;
+
+/// A day's `Solution` with its types erased, so days can be picked at runtime.
+pub trait Puzzle {
+    fn input_path(&self) -> &'static str;
+
+    fn parse(&self, raw: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    /// Solves `part` for an input returned by this puzzle's `parse`.
+    fn solve(&self, inp: &dyn Any, part: u8) -> String;
+}
+
+struct Day<S>(PhantomData<S>);
+
+impl<S: Solution> Puzzle for Day<S>
+where
+    S::Input: 'static,
+{
+    fn input_path(&self) -> &'static str {
+        S::INPUT_PATH
+    }
+
+    fn parse(&self, raw: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(raw)?))
+    }
+
+    fn solve(&self, inp: &dyn Any, part: u8) -> String {
+        let inp = inp
+            .downcast_ref::<S::Input>()
+            .expect("input was parsed by another puzzle");
+
+        if part == 1 {
+            S::part1(inp).to_string()
+        } else {
+            S::part2(inp).to_string()
+        }
+    }
+}
+
+pub fn puzzle(day: u8) -> Option<Box<dyn Puzzle>> {
+    let puzzle: Box<dyn Puzzle> = match day {
+        1 => Box::new(Day::<CalorieElf>(PhantomData)),
+        2 => Box::new(Day::<RockPaperSissor>(PhantomData)),
+        3 => Box::new(Day::<RucksackReorg>(PhantomData)),
+        4 => Box::new(Day::<CampCleanup>(PhantomData)),
+        5 => Box::new(Day::<SupplyStacks>(PhantomData)),
+        6 => Box::new(Day::<TuningTrouble>(PhantomData)),
+        7 => Box::new(Day::<NoSpace>(PhantomData)),
+        8 => Box::new(Day::<TreetopTreeHouse>(PhantomData)),
+        _ => return None,
+    };
+
+    Some(puzzle)
+}
+
+/// Reads the raw input from `source`, or from the day's `res/` file, which is
+/// downloaded first when missing or empty and a session token is configured.
+pub fn read_input(
+    puzzle: &dyn Puzzle,
+    day: u8,
+    source: Option<&InputSource>,
+) -> Result<String, String> {
+    match source {
+        Some(source) => source
+            .read()
+            .map_err(|err| format!("unable to read input from {source}: {err}")),
+        None => fetch::resolve(puzzle.input_path(), day, Fetcher::from_env().as_ref()),
+    }
+}
+
+/// Solves each of `parts` of `day`, reading the input once from `source` or
+/// the day's `res/` file.
+pub fn run(day: u8, parts: &[u8], source: Option<&InputSource>) -> Result<Vec<String>, String> {
+    if let Some(part) = parts.iter().find(|part| **part != 1 && **part != 2) {
+        return Err(format!("day {day} has no part {part}"));
+    }
+
+    let puzzle = puzzle(day).ok_or_else(|| format!("day {day} is not implemented"))?;
+    let raw = read_input(puzzle.as_ref(), day, source)?;
+    let inp = puzzle.parse(&raw).map_err(|err| err.to_string())?;
+
+    let ans = parts.iter().map(|part| puzzle.solve(inp.as_ref(), *part));
+    Ok(ans.collect())
+}
+
+/// Times parsing and both parts of `day` over `runs` repetitions. A panic
+/// while parsing or solving is reported as an error.
+pub fn bench(day: u8, runs: usize) -> Result<Bench, String> {
+    let puzzle = puzzle(day).ok_or_else(|| format!("day {day} is not implemented"))?;
+    let raw = read_input(puzzle.as_ref(), day, None)?;
+    quietly(|| bench_puzzle(puzzle.as_ref(), &raw, runs))
+}
+
+fn bench_puzzle(puzzle: &dyn Puzzle, raw: &str, runs: usize) -> Result<Bench, String> {
+    if runs == 0 {
+        return Err(String::from("runs must be at least 1"));
+    }
+
+    catch_panic(|| {
+        let (mut parse, mut part1, mut part2) = (vec![], vec![], vec![]);
+
+        for _ in 0..runs {
+            let start_time = Instant::now();
+            let inp = puzzle
+                .parse(black_box(raw))
+                .map_err(|err| err.to_string())?;
+            parse.push(start_time.elapsed());
+
+            let start_time = Instant::now();
+            black_box(puzzle.solve(inp.as_ref(), 1));
+            part1.push(start_time.elapsed());
+
+            let start_time = Instant::now();
+            black_box(puzzle.solve(inp.as_ref(), 2));
+            part2.push(start_time.elapsed());
+        }
+
+        Ok(Bench {
+            parse: Timing::new(parse),
+            part1: Timing::new(part1),
+            part2: Timing::new(part2),
+        })
+    })?
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Solved { answer: String, elapsed: Duration },
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+/// Solves both parts of every day against its `res/` input. A day that fails
+/// to read, parse or solve is reported as such instead of stopping the run.
+pub fn run_all() -> Vec<Row> {
+    quietly(solve_all)
+}
+
+fn solve_all() -> Vec<Row> {
+    let mut rows = vec![];
+
+    for day in DAYS {
+        let puzzle = puzzle(day).expect("every day in DAYS has a puzzle");
+        let inp = read_input(puzzle.as_ref(), day, None)
+            .and_then(|raw| catch_panic(|| puzzle.parse(&raw))?.map_err(|err| err.to_string()));
+
+        for part in [1, 2] {
+            let outcome = match &inp {
+                Ok(inp) => {
+                    let start_time = Instant::now();
+                    match catch_panic(|| puzzle.solve(inp.as_ref(), part)) {
+                        Ok(answer) => Outcome::Solved {
+                            answer,
+                            elapsed: start_time.elapsed(),
+                        },
+                        Err(err) => Outcome::Failed(err),
+                    }
+                }
+                Err(err) => Outcome::Failed(err.clone()),
+            };
+
+            rows.push(Row { day, part, outcome });
+        }
+    }
+
+    rows
+}
+
+/// Renders `rows` as a table, checking each answer against `store`.
+pub fn summary(rows: &[Row], store: &AnswerStore) -> String {
+    let mut table = format!(
+        "{:>3}  {:>4}  {:<16}  {:>10}  {}\n",
+        "day", "part", "answer", "elapsed", "status"
+    );
+
+    for row in rows.iter() {
+        let (answer, elapsed, status) = match &row.outcome {
+            Outcome::Solved { answer, elapsed } => (
+                answer.as_str(),
+                format!("{elapsed:.1?}"),
+                store.verify(row.day, row.part, answer).to_string(),
+            ),
+            Outcome::Failed(err) => ("-", String::from("-"), format!("failed: {err}")),
+        };
+
+        let _ = writeln!(
+            table,
+            "{:>3}  {:>4}  {:<16}  {:>10}  {}",
+            row.day, row.part, answer, elapsed, status
+        );
+    }
+
+    table
+}
+
+/// Runs `f` with the panic hook silenced, so that the panics `catch_panic`
+/// reports do not also print over the output. The hook is shared by every
+/// thread, so this wraps whole runs rather than each `catch_panic`.
+fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+/// Runs `f`, turning a panic into an error with the panic's message.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let msg = match payload.downcast_ref::<&str>() {
+            Some(msg) => String::from(*msg),
+            None => payload
+                .downcast_ref::<String>()
+                .cloned()
+                .unwrap_or_else(|| String::from("unknown panic")),
+        };
+        format!("panicked: {msg}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_panic_reports_message() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+        assert_eq!(
+            catch_panic(|| -> u8 { panic!("boom") }),
+            Err(String::from("panicked: boom"))
+        );
+    }
+
+    /// Parses anything and panics on part 2.
+    struct Fragile;
+
+    impl Puzzle for Fragile {
+        fn input_path(&self) -> &'static str {
+            "res/fragile.txt"
+        }
+
+        fn parse(&self, raw: &str) -> Result<Box<dyn Any>, ParseError> {
+            Ok(Box::new(String::from(raw)))
+        }
+
+        fn solve(&self, _inp: &dyn Any, part: u8) -> String {
+            match part {
+                1 => String::from("fine"),
+                _ => panic!("boom"),
+            }
+        }
+    }
+
+    #[test]
+    fn bench_reports_failures() {
+        assert_eq!(
+            bench_puzzle(&Fragile, "", 3).map(|_| ()),
+            Err(String::from("panicked: boom"))
+        );
+        assert_eq!(
+            bench_puzzle(&Fragile, "", 0).map(|_| ()),
+            Err(String::from("runs must be at least 1"))
+        );
+        assert!(bench(1, 0).is_err());
+    }
+
+    #[test]
+    fn summary_marks_failures() {
+        let rows = [
+            Row {
+                day: 1,
+                part: 1,
+                outcome: Outcome::Solved {
+                    answer: String::from("71934"),
+                    elapsed: Duration::from_micros(5),
+                },
+            },
+            Row {
+                day: 8,
+                part: 2,
+                outcome: Outcome::Failed(String::from("panicked: boom")),
+            },
+        ];
+        let store = AnswerStore::new("1 1 accepted 71934").unwrap();
+
+        let table = summary(&rows, &store);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[1].ends_with("correct"), "{table}");
+        assert!(lines[2].ends_with("failed: panicked: boom"), "{table}");
+    }
+}
@@ -0,0 +1,371 @@
+use std::{fs, path::Path};
+
+use regex::{Captures, Regex};
+
+const LIB_PATH: &str = "src/lib.rs";
+const RUNNER_PATH: &str = "src/runner.rs";
+
+/// A new day's module, filled in by `NewDay::source`.
+const TEMPLATE: &str = r#"use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+const DAY: u8 = __DAY__;
+
+pub fn get_inputs(raw: &str) -> Result<Vec<String>, ParseError> {
+    let inp = lines(raw);
+    if inp.is_empty() {
+        return Err(ParseError::eof(DAY, 1, "the puzzle input"));
+    }
+
+    Ok(inp
+        .into_iter()
+        .map(|(_, text)| String::from(text))
+        .collect())
+}
+
+// sol 1
+pub fn part1(inp: &[String]) -> i32 {
+    todo!("part 1 of {} lines", inp.len())
+}
+
+// sol 2
+pub fn part2(inp: &[String]) -> i32 {
+    todo!("part 2 of {} lines", inp.len())
+}
+
+pub struct __STRUCT__;
+
+impl Solution for __STRUCT__ {
+    const INPUT_PATH: &'static str = "res/__MODULE__.txt";
+
+    type Input = Vec<String>;
+    type Output = i32;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError> {
+        get_inputs(raw)
+    }
+
+    fn part1(inp: &Self::Input) -> Self::Output {
+        part1(inp)
+    }
+
+    fn part2(inp: &Self::Input) -> Self::Output {
+        part2(inp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn part1_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(part1(&inp), 0);
+    }
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn part2_example() {
+        let inp = get_inputs(EXAMPLE).unwrap();
+        assert_eq!(part2(&inp), 0);
+    }
+}
+"#;
+
+#[derive(Debug, PartialEq)]
+pub struct NewDay {
+    pub day: u8,
+    /// Puzzle name in snake case, e.g. `rope_bridge`.
+    pub slug: String,
+}
+
+impl NewDay {
+    /// `name` may be given as the puzzle title, e.g. `Rope Bridge`.
+    pub fn new(day: u8, name: &str) -> Result<NewDay, String> {
+        let slug = name
+            .to_lowercase()
+            .split(|chr: char| !chr.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<&str>>()
+            .join("_");
+
+        // The name also makes the solution struct's name
+        if !slug.starts_with(|chr: char| chr.is_ascii_alphabetic()) {
+            return Err(format!("day name `{name}` must start with a letter"));
+        }
+
+        Ok(NewDay { day, slug })
+    }
+
+    /// Module name, e.g. `_9_rope_bridge`.
+    pub fn module(&self) -> String {
+        format!("_{}_{}", self.day, self.slug)
+    }
+
+    /// Solution struct name, e.g. `RopeBridge`.
+    pub fn struct_name(&self) -> String {
+        self.slug
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn source(&self) -> String {
+        TEMPLATE
+            .replace("__DAY__", &self.day.to_string())
+            .replace("__MODULE__", &self.module())
+            .replace("__STRUCT__", &self.struct_name())
+    }
+
+    /// Declares the module in `lib_rs`.
+    pub fn register_module(&self, lib_rs: &str) -> Result<String, String> {
+        let day_mod = Regex::new(r"(?m)^pub mod (_\d+_\w+);\n").unwrap();
+        let declaration = format!("pub mod {};\n", self.module());
+
+        insert_sorted(lib_rs, &day_mod, &self.module(), &declaration)
+            .ok_or_else(|| format!("no day module declared in {LIB_PATH}"))
+    }
+
+    /// Adds the day to `runner_rs`: its import and its entry in `puzzle`, after
+    /// the last one, which must be the day before. The day stays out of `DAYS`
+    /// until it is solved, so that `run-all` and `bench` leave the stub alone.
+    pub fn register_puzzle(&self, runner_rs: &str) -> Result<String, String> {
+        let last_puzzle =
+            Regex::new(r"(?m)^( +)(\d+) => Box::new\(Day::<\w+>\(PhantomData\)\),\n").unwrap();
+        let caps = last_puzzle
+            .captures_iter(runner_rs)
+            .last()
+            .ok_or_else(|| format!("no puzzle registered in {RUNNER_PATH}"))?;
+        let last_day: u8 = caps[2].parse().unwrap();
+        if self.day != last_day + 1 {
+            return Err(format!(
+                "the next day is {}, not {}",
+                last_day + 1,
+                self.day
+            ));
+        }
+        let (at, indent) = (caps.get(0).unwrap().end(), &caps[1]);
+        let runner_rs = format!(
+            "{}{indent}{} => Box::new(Day::<{}>(PhantomData)),\n{}",
+            &runner_rs[..at],
+            self.day,
+            self.struct_name(),
+            &runner_rs[at..]
+        );
+
+        let day_use = Regex::new(r"(?m)^use crate::(_\d+_\w+)::\w+;\n").unwrap();
+        let import = format!("use crate::{}::{};\n", self.module(), self.struct_name());
+
+        insert_sorted(&runner_rs, &day_use, &self.module(), &import)
+            .ok_or_else(|| format!("no day imported in {RUNNER_PATH}"))
+    }
+
+    /// Writes the module and an empty input under `root` and registers the
+    /// day, returning the files that were created.
+    pub fn create(&self, root: impl AsRef<Path>) -> Result<Vec<String>, String> {
+        let root = root.as_ref();
+        let module_path = format!("src/{}.rs", self.module());
+        let input_path = format!("res/{}.txt", self.module());
+
+        for path in [&module_path, &input_path] {
+            if root.join(path).exists() {
+                return Err(format!("{path} already exists"));
+            }
+        }
+
+        let read = |path: &str| {
+            fs::read_to_string(root.join(path))
+                .map_err(|err| format!("unable to read {path}: {err}"))
+        };
+        let write = |path: &str, content: &str| {
+            fs::write(root.join(path), content)
+                .map_err(|err| format!("unable to write {path}: {err}"))
+        };
+
+        // Check both registrations before touching anything
+        let lib_rs = self.register_module(&read(LIB_PATH)?)?;
+        let runner_rs = self.register_puzzle(&read(RUNNER_PATH)?)?;
+
+        write(&module_path, &self.source())?;
+        write(&input_path, "")?;
+        write(LIB_PATH, &lib_rs)?;
+        write(RUNNER_PATH, &runner_rs)?;
+
+        Ok(vec![module_path, input_path])
+    }
+}
+
+/// Inserts `new_line` among the lines matching `line` where rustfmt sorts it,
+/// that is before the first whose module, the first capture, comes after
+/// `module` as a string. `_10_x` thus goes before `_1_x`.
+fn insert_sorted(text: &str, line: &Regex, module: &str, new_line: &str) -> Option<String> {
+    let lines: Vec<Captures> = line.captures_iter(text).collect();
+    let after_last = lines.last()?.get(0).unwrap().end();
+    let at = lines
+        .iter()
+        .find(|caps| &caps[1] > module)
+        .map_or(after_last, |caps| caps.get(0).unwrap().start());
+
+    Some(format!("{}{new_line}{}", &text[..at], &text[at..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Write, process::Command};
+
+    use super::*;
+
+    #[test]
+    fn name_new_day() {
+        let new_day = NewDay::new(9, "Rope Bridge").unwrap();
+        assert_eq!(new_day.slug, "rope_bridge");
+        assert_eq!(new_day.module(), "_9_rope_bridge");
+        assert_eq!(new_day.struct_name(), "RopeBridge");
+        assert!(new_day
+            .source()
+            .contains("const INPUT_PATH: &'static str = \"res/_9_rope_bridge.txt\";"));
+
+        assert!(NewDay::new(9, " -- ").is_err());
+        assert!(NewDay::new(9, "9 lives").is_err());
+    }
+
+    #[test]
+    fn template_compiles_without_warnings() {
+        let new_day = NewDay::new(9, "rope_bridge").unwrap();
+        let dir =
+            std::env::temp_dir().join(format!("advent_of_code_scaffold_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // The template only needs these modules of the crate
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut lib_rs = String::new();
+        for module in ["error", "input", "solution"] {
+            let path = src.join(format!("{module}.rs"));
+            let _ = writeln!(lib_rs, "#[path = {path:?}]\npub mod {module};");
+        }
+        let path = dir.join(format!("{}.rs", new_day.module()));
+        fs::write(&path, new_day.source()).unwrap();
+        let _ = writeln!(lib_rs, "#[path = {path:?}]\npub mod {};", new_day.module());
+        fs::write(dir.join("lib.rs"), lib_rs).unwrap();
+
+        // Once as the library, then with its tests, which leave most of it unused
+        for args in [&["--crate-type", "lib"][..], &["--test", "-A", "dead_code"]] {
+            let output = match Command::new("rustc")
+                .args(["--edition", "2021", "-D", "warnings", "--out-dir"])
+                .arg(&dir)
+                .args(args)
+                .arg(dir.join("lib.rs"))
+                .output()
+            {
+                Ok(output) => output,
+                // Nothing to check against without rustc
+                Err(_) => break,
+            };
+            assert!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    const LIB_RS: &str = "\
+pub mod _7_no_space;
+pub mod _8_treetop_tree_house;
+pub mod answers;
+";
+
+    const RUNNER_RS: &str = "\
+use crate::_8_treetop_tree_house::TreetopTreeHouse;
+use crate::answers::AnswerStore;
+
+pub const DAYS: RangeInclusive<u8> = 1..=8;
+
+pub fn puzzle(day: u8) -> Option<Box<dyn Puzzle>> {
+    let puzzle: Box<dyn Puzzle> = match day {
+        8 => Box::new(Day::<TreetopTreeHouse>(PhantomData)),
+        _ => return None,
+    };
+";
+
+    #[test]
+    fn register_with_the_runner() {
+        let new_day = NewDay::new(9, "rope_bridge").unwrap();
+
+        assert_eq!(
+            new_day.register_module(LIB_RS).unwrap(),
+            "\
+pub mod _7_no_space;
+pub mod _8_treetop_tree_house;
+pub mod _9_rope_bridge;
+pub mod answers;
+"
+        );
+        assert_eq!(
+            new_day.register_puzzle(RUNNER_RS).unwrap(),
+            "\
+use crate::_8_treetop_tree_house::TreetopTreeHouse;
+use crate::_9_rope_bridge::RopeBridge;
+use crate::answers::AnswerStore;
+
+pub const DAYS: RangeInclusive<u8> = 1..=8;
+
+pub fn puzzle(day: u8) -> Option<Box<dyn Puzzle>> {
+    let puzzle: Box<dyn Puzzle> = match day {
+        8 => Box::new(Day::<TreetopTreeHouse>(PhantomData)),
+        9 => Box::new(Day::<RopeBridge>(PhantomData)),
+        _ => return None,
+    };
+"
+        );
+
+        let skipped = NewDay::new(10, "cathode_ray_tube").unwrap();
+        assert_eq!(
+            skipped.register_puzzle(RUNNER_RS),
+            Err(String::from("the next day is 9, not 10"))
+        );
+
+        // Sorted as strings by rustfmt, day 10 comes before day 1
+        let lib_rs = new_day.register_module(LIB_RS).unwrap();
+        let runner_rs = new_day.register_puzzle(RUNNER_RS).unwrap();
+        let lib_rs = format!("pub mod _1_calorie_elf;\n{lib_rs}");
+        let runner_rs = format!("use crate::_1_calorie_elf::CalorieElf;\n{runner_rs}");
+        let day_10 = NewDay::new(10, "cathode_ray_tube").unwrap();
+
+        assert!(day_10.register_module(&lib_rs).unwrap().starts_with(
+            "\
+pub mod _10_cathode_ray_tube;
+pub mod _1_calorie_elf;
+pub mod _7_no_space;
+"
+        ));
+        let runner_rs = day_10.register_puzzle(&runner_rs).unwrap();
+        assert!(runner_rs.starts_with(
+            "\
+use crate::_10_cathode_ray_tube::CathodeRayTube;
+use crate::_1_calorie_elf::CalorieElf;
+use crate::_8_treetop_tree_house::TreetopTreeHouse;
+"
+        ));
+        assert!(runner_rs.contains(
+            "\
+        9 => Box::new(Day::<RopeBridge>(PhantomData)),
+        10 => Box::new(Day::<CathodeRayTube>(PhantomData)),
+"
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+use crate::error::ParseError;
+
+/// A day's puzzle: how its input is parsed and how each part is solved.
+pub trait Solution {
+    /// Where the puzzle input lives when no other source is given.
+    const INPUT_PATH: &'static str;
+
+    type Input;
+    type Output: Display;
+
+    fn parse(raw: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(inp: &Self::Input) -> Self::Output;
+
+    fn part2(inp: &Self::Input) -> Self::Output;
+}
+
+/// Solves both parts against the day's committed `res/` input.
+#[cfg(test)]
+pub fn solve_real_input<S: Solution>() -> (S::Output, S::Output) {
+    let raw = std::fs::read_to_string(S::INPUT_PATH).unwrap();
+    let inp = S::parse(&raw).unwrap();
+    (S::part1(&inp), S::part2(&inp))
+}
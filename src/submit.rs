@@ -0,0 +1,315 @@
+use std::{
+    fmt, fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+
+use crate::answers::{check_value, Answer, AnswerStore, Status, Verdict};
+use crate::fetch::Fetcher;
+
+/// When the next answer may be submitted, in seconds since the unix epoch.
+pub const COOLDOWN_PATH: &str = "res/cooldown.txt";
+
+/// What the puzzle site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feedback {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked, another one was submitted too recently.
+    Wait,
+    /// The part was already solved, or its first part was not yet.
+    WrongLevel,
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feedback::Right => write!(f, "right answer"),
+            Feedback::Wrong => write!(f, "wrong answer"),
+            Feedback::TooHigh => write!(f, "wrong answer, too high"),
+            Feedback::TooLow => write!(f, "wrong answer, too low"),
+            Feedback::Wait => write!(f, "not checked, submitted too recently"),
+            Feedback::WrongLevel => write!(f, "not checked, already solved or locked"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+    pub feedback: Feedback,
+    /// How long to hold off before submitting again.
+    pub wait: Option<Duration>,
+}
+
+impl Reply {
+    /// Reads the reply page the site answers a submission with.
+    pub fn new(page: &str) -> Option<Reply> {
+        let feedback = if page.contains("That's the right answer") {
+            Feedback::Right
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Feedback::TooHigh
+            } else if page.contains("your answer is too low") {
+                Feedback::TooLow
+            } else {
+                Feedback::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Feedback::Wait
+        } else if page.contains("You don't seem to be solving the right level") {
+            Feedback::WrongLevel
+        } else {
+            return None;
+        };
+
+        Some(Reply {
+            feedback,
+            wait: Reply::get_wait(page),
+        })
+    }
+
+    fn get_wait(page: &str) -> Option<Duration> {
+        let left_to_wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        if let Some(caps) = left_to_wait.captures(page) {
+            let minutes: u64 = caps.get(1).map_or(0, |min| min.as_str().parse().unwrap());
+            let seconds: u64 = caps[2].parse().unwrap();
+            return Some(Duration::from_secs(minutes * 60 + seconds));
+        }
+
+        let wait_minutes = Regex::new(r"(?i)wait (one|\d+) minutes? before trying again").unwrap();
+        let caps = wait_minutes.captures(page)?;
+        let minutes: u64 = match &caps[1] {
+            "one" => 1,
+            minutes => minutes.parse().unwrap(),
+        };
+        Some(Duration::from_secs(minutes * 60))
+    }
+}
+
+/// Refuses `value` when it could not be saved, or when `store` already knows
+/// how the site will judge it.
+pub fn check(store: &AnswerStore, day: u8, part: u8, value: &str) -> Result<(), String> {
+    check_value(value)?;
+
+    match store.verify(day, part, value) {
+        Verdict::Correct => {
+            return Err(format!(
+                "day {day} part {part} is already solved with {value}"
+            ))
+        }
+        Verdict::Incorrect { expected } => {
+            return Err(format!(
+                "day {day} part {part} is already solved with {expected}"
+            ))
+        }
+        Verdict::KnownWrong => return Err(format!("{value} was already rejected")),
+        Verdict::New => (),
+    }
+
+    let Ok(number) = value.parse::<i64>() else {
+        return Ok(());
+    };
+    for answer in store.answers(day, part) {
+        let Ok(bound) = answer.value.parse::<i64>() else {
+            continue;
+        };
+
+        match answer.status {
+            Status::TooHigh if number >= bound => {
+                return Err(format!("{value} is not below {bound}, which is too high"))
+            }
+            Status::TooLow if number <= bound => {
+                return Err(format!("{value} is not above {bound}, which is too low"))
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the end of the running cooldown, if any was ever saved at `path`.
+pub fn load_cooldown(path: impl AsRef<Path>) -> Result<Option<SystemTime>, String> {
+    let path = path.as_ref();
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("unable to read {}: {err}", path.display())),
+    };
+
+    let seconds: u64 = raw
+        .trim()
+        .parse()
+        .map_err(|_| format!("{}: expected seconds since the unix epoch", path.display()))?;
+    Ok(Some(UNIX_EPOCH + Duration::from_secs(seconds)))
+}
+
+pub fn save_cooldown(path: impl AsRef<Path>, until: SystemTime) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // Round up so the saved cooldown never ends early
+    let until = until.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = until.as_secs() + u64::from(until.subsec_nanos() > 0);
+    fs::write(path, format!("{seconds}\n"))
+}
+
+/// Submits `value` as the answer to `part` of `day` at `now`, unless `check`
+/// refuses it or the cooldown saved at `cooldown_path` is still running. The
+/// verdict is recorded in `store`, and any wait the site asks for becomes the
+/// new cooldown.
+pub fn submit(
+    fetcher: &Fetcher,
+    store: &mut AnswerStore,
+    cooldown_path: impl AsRef<Path>,
+    (day, part, value): (u8, u8, &str),
+    now: SystemTime,
+) -> Result<Reply, String> {
+    check(store, day, part, value)?;
+
+    let cooldown_path = cooldown_path.as_ref();
+    if let Some(until) = load_cooldown(cooldown_path)? {
+        if let Ok(left) = until.duration_since(now) {
+            return Err(format!(
+                "wait {}s before submitting again",
+                left.as_secs().max(1)
+            ));
+        }
+    }
+
+    let page = fetcher.submit(day, part, value)?;
+    let reply =
+        Reply::new(&page).ok_or_else(|| String::from("unrecognised reply to the answer"))?;
+
+    let status = match reply.feedback {
+        Feedback::Right => Some(Status::Accepted),
+        Feedback::Wrong => Some(Status::Rejected),
+        Feedback::TooHigh => Some(Status::TooHigh),
+        Feedback::TooLow => Some(Status::TooLow),
+        Feedback::Wait | Feedback::WrongLevel => None,
+    };
+    if let Some(status) = status {
+        store.record(Answer {
+            day,
+            part,
+            status,
+            value: String::from(value),
+        })?;
+    }
+
+    if let Some(wait) = reply.wait {
+        save_cooldown(cooldown_path, now + wait)
+            .map_err(|err| format!("unable to write {}: {err}", cooldown_path.display()))?;
+    }
+
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::mock_server;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is \
+        too high.  If you're stuck, make sure you're using the full input data. Please \
+        wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>\
+        </p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to \
+        wait after submitting an answer before trying again.  You have 4m 29s left to \
+        wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>";
+
+    #[test]
+    fn read_replies() {
+        assert_eq!(
+            Reply::new(TOO_HIGH),
+            Some(Reply {
+                feedback: Feedback::TooHigh,
+                wait: Some(Duration::from_secs(60)),
+            })
+        );
+        assert_eq!(
+            Reply::new(TOO_RECENT),
+            Some(Reply {
+                feedback: Feedback::Wait,
+                wait: Some(Duration::from_secs(269)),
+            })
+        );
+        assert_eq!(
+            Reply::new("<p>That's the right answer!  You are one gold star closer.</p>"),
+            Some(Reply {
+                feedback: Feedback::Right,
+                wait: None,
+            })
+        );
+        assert_eq!(
+            Reply::new(
+                "<p>That's not the right answer. Please wait 5 minutes before trying again.</p>"
+            )
+            .unwrap()
+            .wait,
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(Reply::new("<p>Nothing to see here</p>"), None);
+    }
+
+    #[test]
+    fn refuse_known_answers() {
+        let store = AnswerStore::new(
+            "\
+1 1 accepted 71934
+1 2 rejected 211447
+1 2 too-high 300000
+1 2 too-low 100000
+",
+        )
+        .unwrap();
+
+        assert!(check(&store, 1, 1, "70000").is_err());
+        assert_eq!(
+            check(&store, 1, 2, "211447"),
+            Err(String::from("211447 was already rejected"))
+        );
+        assert!(check(&store, 1, 2, "300001").is_err());
+        assert!(check(&store, 1, 2, "99999").is_err());
+        assert_eq!(check(&store, 1, 2, "200000"), Ok(()));
+        assert_eq!(
+            check(&store, 1, 2, ""),
+            Err(String::from("the answer is empty"))
+        );
+        assert!(check(&store, 1, 2, "200 000").is_err());
+    }
+
+    #[test]
+    fn submit_records_reply_and_cooldown() {
+        let (base_url, server) = mock_server("200 OK", TOO_HIGH);
+        let fetcher = Fetcher::new(&base_url, "secret");
+        let mut store = AnswerStore::default();
+        let cooldown_path =
+            std::env::temp_dir().join(format!("cooldown_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&cooldown_path);
+        let now = UNIX_EPOCH + Duration::from_secs(1_670_000_000);
+
+        let reply = submit(&fetcher, &mut store, &cooldown_path, (1, 2, "211447"), now);
+        assert_eq!(reply.unwrap().feedback, Feedback::TooHigh);
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(request.last().unwrap(), "level=2&answer=211447");
+        assert_eq!(store.verify(1, 2, "211447"), Verdict::KnownWrong);
+        assert_eq!(
+            load_cooldown(&cooldown_path),
+            Ok(Some(now + Duration::from_secs(60)))
+        );
+
+        // Refused locally, the mock server is gone
+        let err = submit(&fetcher, &mut store, &cooldown_path, (1, 2, "200000"), now);
+        assert_eq!(err, Err(String::from("wait 60s before submitting again")));
+        fs::remove_file(&cooldown_path).unwrap();
+    }
+}